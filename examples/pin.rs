@@ -4,6 +4,9 @@ use fuels::prelude::Salt;
 use fuels::types::bech32::Bech32Address;
 use fuels::types::{Address, EvmAddress, Identity};
 use guild_pin_contract::contract::{GuildAction, GuildPinContract};
+use guild_pin_contract::error::{GuildPinError, ParametersError};
+use guild_pin_contract::indexer::{Indexer, JsonLinesStore};
+use guild_pin_contract::keystore::Password;
 use guild_pin_contract::metadata::TokenUri;
 use guild_pin_contract::parameters::Parameters;
 use guild_pin_contract::parameters::ParametersBuilder;
@@ -37,15 +40,40 @@ struct Pin {
     deployer: PathBuf,
     /// Path to the treasury's secret key file.
     ///
-    /// This will be the address where minting fees are accumulated.
+    /// This will be the address where minting fees are accumulated. It is ignored if
+    /// `--treasury-address` is provided.
     #[structopt(default_value = "../wallets/fuel-tn-treasury-sk")]
     treasury: PathBuf,
+    /// Hex encoded address of the treasury.
+    ///
+    /// The treasury never signs transactions, so its secret key is not necessarily needed.
+    #[structopt(long)]
+    treasury_address: Option<String>,
+    /// Treat the signer, deployer and treasury files as encrypted JSON keystores.
+    #[structopt(long)]
+    keystore: bool,
+    /// Load the deployer from the given account of a forc-wallet vault.
+    ///
+    /// If set, the deployer path should point to the vault, e.g. `~/.fuel/wallets/.wallet`.
+    #[structopt(long)]
+    forc_wallet_account: Option<usize>,
+    /// Environment variable holding the password of the encrypted keys.
+    ///
+    /// The password is prompted for if this is not set.
+    #[structopt(long)]
+    password_env: Option<String>,
     /// Current deployment version of the contract.
     ///
     /// If you re-deploy the contract with a bumped version, it will start with a new state at a
     /// new address that's derived from a salt generated from the version number.
     #[structopt(default_value = "1")]
     version: u8,
+    /// Deploy the contract in soulbound mode, in which the contract keeps custody of every pin.
+    ///
+    /// The mode is part of the contract's configuration, so it has to be set for every command
+    /// in order to derive the right contract address.
+    #[structopt(long)]
+    soulbound: bool,
     /// Optional contract interaction command.
     #[structopt(subcommand)]
     contract: Option<Contract>,
@@ -68,6 +96,17 @@ enum Contract {
         #[structopt(default_value = "15")]
         fee: u64,
     },
+    /// An admin-level contract call that overrides the minting fee in a guild and/or of an action.
+    ///
+    /// The override is removed if the fee is not submitted.
+    SetFeeOverride {
+        #[structopt(short = "g", long)]
+        guild_id: Option<u64>,
+        /// One of `joined`, `owner`, `admin` and `role`, the latter applying to every role.
+        #[structopt(short = "a", long)]
+        action: Option<String>,
+        fee: Option<u64>,
+    },
     /// An admin-level contract call that sets the treasury address collecting minting fees.
     SetTreasury { treasury: String },
     /// Dispatches a test claim for minting a Guild pin.
@@ -79,6 +118,9 @@ enum Contract {
         user_id: u64,
         #[structopt(short = "g", long)]
         guild_id: u64,
+        /// Required by the `role` action.
+        #[structopt(short = "r", long)]
+        role_id: Option<u64>,
         #[structopt(default_value = "owner")]
         action: String,
     },
@@ -89,22 +131,71 @@ enum Contract {
         #[structopt(short = "p", long)]
         pin_id: Option<u64>,
     },
+    /// Lists the assets held by the contract.
+    Balances,
+    /// An admin-level contract call that withdraws every asset held by the contract.
+    ///
+    /// If the recipient address is not submitted, the assets are sent to the treasury.
+    Sweep {
+        #[structopt(long)]
+        to: Option<String>,
+    },
+    /// Compares the pins recorded in the contract with the pin assets actually held by their
+    /// owners and prints a drift report.
+    Reconcile {
+        /// Print the report as CSV instead of JSON.
+        #[structopt(long)]
+        csv: bool,
+        /// Additional address to check for pins recorded for someone else, may be repeated.
+        #[structopt(long)]
+        holder: Vec<String>,
+    },
+    /// Indexes the events emitted by the contract into a local json-lines file.
+    ///
+    /// A checkpoint is stored next to the file, so subsequent runs resume where the previous one
+    /// stopped.
+    Index {
+        #[structopt(long, default_value = "pins.jsonl")]
+        db: PathBuf,
+        /// Block height to start from if there's no checkpoint yet.
+        #[structopt(long, default_value = "0")]
+        from: u32,
+    },
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), ParametersError> {
     let pin = Pin::from_args();
 
     println!("pin: {:#?}", pin);
 
-    let parameters = ParametersBuilder::new()
-        .signer_file(pin.signer)
-        .owner_file(pin.deployer)
-        .treasury_file(pin.treasury)
+    let password = pin
+        .password_env
+        .map(Password::Env)
+        .unwrap_or(Password::Prompt);
+    let builder = if pin.keystore {
+        ParametersBuilder::new().signer_keystore(pin.signer, &password)?
+    } else {
+        ParametersBuilder::new().signer_file(pin.signer)?
+    };
+    let builder = match pin.forc_wallet_account {
+        Some(index) => builder.owner_forc_wallet(pin.deployer, index, &password)?,
+        None if pin.keystore => builder.owner_keystore(pin.deployer, &password)?,
+        None => builder.owner_file(pin.deployer)?,
+    };
+    let builder = match pin.treasury_address {
+        Some(treasury) => {
+            builder.treasury(Address::from_str(&treasury).expect("invalid treasury address"))
+        }
+        None if pin.keystore => builder.treasury_keystore(pin.treasury, &password)?,
+        None => builder.treasury_file(pin.treasury)?,
+    };
+    let parameters = builder
         .url(&pin.url)
         .salt(Salt::new([pin.version; 32]))
+        .soulbound(pin.soulbound)
         .build()
-        .await;
+        .await?;
 
     // print addresses and balances
     print_balances(&parameters).await;
@@ -123,15 +214,25 @@ async fn main() {
     match pin.contract {
         Some(Contract::SetSigner { signer }) => set_signer(&parameters, &contract, signer).await,
         Some(Contract::SetFee { fee }) => set_fee(&parameters, &contract, fee).await,
+        Some(Contract::SetFeeOverride {
+            guild_id,
+            action,
+            fee,
+        }) => {
+            // overrides ignore the role id
+            let action = action.map(|action| parse_action(&action, Some(0)));
+            set_fee_override(&parameters, &contract, guild_id, action, fee).await
+        }
         Some(Contract::SetTreasury { treasury }) => {
             set_treasury(&parameters, &contract, &treasury).await
         }
         Some(Contract::TestClaim {
             user_id,
             guild_id,
+            role_id,
             action,
         }) => {
-            test_claim(&parameters, &contract, user_id, guild_id, action).await;
+            test_claim(&parameters, &contract, user_id, guild_id, role_id, action).await;
             read_last_metadata(&contract).await;
         }
         Some(Contract::Metadata { pin_id }) => {
@@ -141,11 +242,19 @@ async fn main() {
                 read_last_metadata(&contract).await;
             }
         }
+        Some(Contract::Balances) => contract_balances(&contract).await,
+        Some(Contract::Sweep { to }) => sweep(&parameters, &contract, to).await,
+        Some(Contract::Reconcile { csv, holder }) => {
+            reconcile(&parameters, &contract, csv, holder).await
+        }
+        Some(Contract::Index { db, from }) => index(&parameters, &contract, db, from).await,
         _ => unimplemented!(),
     }
 
     print_balances(&parameters).await;
     query_storage(&contract).await;
+
+    Ok(())
 }
 
 async fn eth_balance(provider: &Provider, address: &Bech32Address) -> u64 {
@@ -175,7 +284,10 @@ async fn query_storage(contract: &GuildPinContract) {
     println!("ON-CHAIN QUERIES");
     println!("owner:    {:?}", contract.owner().await.unwrap());
     println!("treasury: {:?}", contract.treasury().await.unwrap());
-    println!("fee:      {}", contract.fee().await.unwrap());
+    match contract.fee().await {
+        Ok(fee) => println!("fee:      {}", fee),
+        Err(error) => println!("fee:      {}", error),
+    }
     println!("signer:   0x{}", signer_in_storage(contract).await);
 }
 
@@ -195,6 +307,23 @@ async fn set_fee(parameters: &Parameters, contract: &GuildPinContract, fee: u64)
     println!("new fee: {:?}", contract.fee().await.unwrap());
 }
 
+async fn set_fee_override(
+    parameters: &Parameters,
+    contract: &GuildPinContract,
+    guild_id: Option<u64>,
+    action: Option<GuildAction>,
+    fee: Option<u64>,
+) {
+    contract
+        .set_fee_override(&parameters.owner, guild_id, action.clone(), fee)
+        .await
+        .unwrap();
+    println!(
+        "new fee override: {:?}",
+        contract.fee_override(guild_id, action).await.unwrap()
+    );
+}
+
 async fn set_treasury(parameters: &Parameters, contract: &GuildPinContract, treasury: &str) {
     let treasury = Address::from_str(treasury).expect("invalid treasury address");
     contract
@@ -204,32 +333,87 @@ async fn set_treasury(parameters: &Parameters, contract: &GuildPinContract, trea
     println!("new treasury: {:?}", contract.treasury().await.unwrap());
 }
 
+async fn contract_balances(contract: &GuildPinContract) {
+    println!("CONTRACT BALANCES");
+    for (asset_id, amount) in contract.contract_balances().await.unwrap() {
+        println!("{:?}: {}", asset_id, amount);
+    }
+}
+
+async fn sweep(parameters: &Parameters, contract: &GuildPinContract, to: Option<String>) {
+    let to = match to {
+        Some(address) => Identity::from(Address::from_str(&address).expect("invalid address")),
+        None => contract.treasury().await.unwrap(),
+    };
+    for (asset_id, amount) in contract.contract_balances().await.unwrap() {
+        match contract
+            .withdraw(&parameters.owner, asset_id, amount, to)
+            .await
+        {
+            Ok(_) => println!("withdrawn {} of {:?}", amount, asset_id),
+            // pins held in custody in soulbound mode
+            Err(GuildPinError::CannotWithdrawPin) => continue,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+async fn reconcile(
+    parameters: &Parameters,
+    contract: &GuildPinContract,
+    csv: bool,
+    holders: Vec<String>,
+) {
+    let candidates = holders
+        .iter()
+        .map(|holder| Address::from_str(holder).expect("invalid holder address"))
+        .collect::<Vec<Address>>();
+    let report =
+        guild_pin_contract::reconcile::reconcile(contract, parameters.provider(), &candidates)
+            .await
+            .unwrap();
+    if csv {
+        print!("{}", report.to_csv());
+    } else {
+        println!("{}", report.to_json().unwrap());
+    }
+    println!("holders with drift: {}", report.drifted().count());
+}
+
 async fn test_claim(
     parameters: &Parameters,
     contract: &GuildPinContract,
     user_id: u64,
     guild_id: u64,
+    role_id: Option<u64>,
     action: String,
 ) {
-    let action = match action.as_str() {
-        "joined" => GuildAction::Joined,
-        "owner" => GuildAction::Owner,
-        "admin" => GuildAction::Admin,
-        s => panic!("invalid action {}", s),
-    };
+    let action = parse_action(&action, role_id);
     // send claim
-    let claim = ClaimBuilder::new(parameters.owner.address().into(), contract.contract_id())
+    let recipient = Address::from(parameters.owner.address());
+    let provider = parameters.provider();
+    let claim = ClaimBuilder::for_provider(recipient, contract.contract_id(), provider)
         .action(action)
         .user_id(user_id)
         .guild_id(guild_id)
         .build();
     let signature = parameters.sign_claim(&claim);
     contract
-        .claim_eth(&parameters.owner, claim, signature)
+        .claim(&parameters.owner, claim, signature)
         .await
         .unwrap();
 }
 
+fn parse_action(action: &str, role_id: Option<u64>) -> GuildAction {
+    match action {
+        "joined" => GuildAction::Joined,
+        "owner" => GuildAction::Owner,
+        "admin" => GuildAction::Admin,
+        "role" => GuildAction::Role(role_id.expect("missing role id")),
+        s => panic!("invalid action {}", s),
+    }
+}
+
 async fn read_metadata(contract: &GuildPinContract, pin_id: u64) {
     let mut header = contract.encoded_metadata(pin_id).await.unwrap();
     let encoded_metadata = header.split_off(29);
@@ -243,3 +427,13 @@ async fn read_last_metadata(contract: &GuildPinContract) {
     let last_pin_id = contract.total_minted().await.unwrap().saturating_sub(1);
     read_metadata(contract, last_pin_id).await;
 }
+
+async fn index(parameters: &Parameters, contract: &GuildPinContract, db: PathBuf, from: u32) {
+    let store = JsonLinesStore::open(db).unwrap();
+    let indexer = Indexer::new(contract, parameters.provider().clone(), store, from);
+    let next_height = indexer.sync().await.unwrap();
+    println!("indexed until block {}", next_height.saturating_sub(1));
+    for (owner, pins) in indexer.store().pins_by_owner().unwrap() {
+        println!("{}: {:?}", owner, pins);
+    }
+}
@@ -1,14 +1,20 @@
+use crate::error::{GuildPinError, Result};
+use crate::events::{decode_events, GuildPinEvent};
 use crate::parameters::Parameters;
+use crate::utils::{claim_message, pin_sub_id};
+use crate::verify::{verify_claim_signature, ClaimPreflight, SIGNATURE_VALIDITY_PERIOD};
 
 use fuels::core::codec::EncoderConfig;
 use fuels::prelude::*;
 use fuels::programs::call_response::FuelCallResponse;
 use fuels::programs::contract::CallParameters;
-use fuels::types::errors::Error;
-use fuels::types::transaction::TxPolicies;
+use fuels::tx::{Receipt, TxId};
+use fuels::types::transaction::{Transaction, TxPolicies};
 use fuels::types::transaction_builders::VariableOutputPolicy;
 use fuels::types::{AssetId, Bits256, ContractId, EvmAddress, Identity, B512};
 
+use std::collections::HashMap;
+
 /// Used when compiled without the `--release` flag.
 #[cfg(debug_assertions)]
 mod abi {
@@ -38,8 +44,29 @@ mod abi {
 
 pub use abi::*;
 
+/// Number of times [`GuildPinContract::mint`] tries to mint before giving up on a stale sub id.
+pub const MINT_ATTEMPTS: u32 = 3;
+
+/// Wrapper around the deployed guild pin contract.
+///
+/// The contract runs in one of two modes, selected by the `SOULBOUND` configurable at deploy time
+/// (see [`ParametersBuilder::soulbound`](crate::parameters::ParametersBuilder::soulbound)):
+/// - by default every pin's asset is minted to its recipient, who is free to move it around,
+/// - in soulbound mode the contract keeps custody of every pin's asset and ownership is only
+///   recorded in storage, so pins can't change hands.
 pub struct GuildPinContract(GuildPin<WalletUnlocked>);
 
+/// Outcome of [`GuildPinContract::claim_batch`].
+#[derive(Debug)]
+pub struct BatchClaimResponse {
+    /// Id of the multicall transaction, `None` if every claim failed simulation
+    pub tx_id: Option<TxId>,
+    /// Receipts of the multicall transaction
+    pub receipts: Vec<Receipt>,
+    /// Index and error of every claim that was left out of the transaction
+    pub failed: Vec<(usize, GuildPinError)>,
+}
+
 impl GuildPinContract {
     pub fn inner(&self) -> &GuildPin<WalletUnlocked> {
         &self.0
@@ -56,6 +83,10 @@ impl GuildPinContract {
             .expect("invalid treasury")
             .with_SIGNER(parameters.signer_b256())
             .expect("invalid signer")
+            .with_CHAIN_ID(*parameters.provider().chain_id())
+            .expect("invalid chain id")
+            .with_SOULBOUND(parameters.soulbound)
+            .expect("invalid soulbound flag")
             .with_FEE(parameters.fee)
             .expect("invalid fee");
         // load storage configuration
@@ -101,8 +132,14 @@ impl GuildPinContract {
         self.bech_contract_id().into()
     }
 
-    pub fn asset_id(&self) -> AssetId {
-        self.bech_contract_id().asset_id(&Bits256::zeroed())
+    /// Decodes every contract event logged in the transaction, in order.
+    pub fn events<T>(&self, response: &FuelCallResponse<T>) -> Result<Vec<GuildPinEvent>> {
+        decode_events(&self.0.log_decoder(), &response.receipts).map_err(GuildPinError::from)
+    }
+
+    /// Returns the asset id of the given pin, every pin being minted under its own sub id.
+    pub fn asset_id(&self, pin_id: u64) -> AssetId {
+        self.bech_contract_id().asset_id(&pin_sub_id(pin_id))
     }
 
     pub async fn initialize(&self, caller: &WalletUnlocked) -> Result<FuelCallResponse<()>> {
@@ -113,6 +150,7 @@ impl GuildPinContract {
             .initialize()
             .call()
             .await
+            .map_err(GuildPinError::from)
     }
 
     pub async fn set_owner(
@@ -127,13 +165,14 @@ impl GuildPinContract {
             .set_owner(owner)
             .call()
             .await
+            .map_err(GuildPinError::from)
     }
 
     pub async fn owner(&self) -> Result<Identity> {
         let state = self.0.methods().owner().simulate().await?.value;
         match state {
             State::Initialized(owner) => Ok(owner),
-            _ => Err(Error::IO("NotInitialized".to_string())),
+            _ => Err(GuildPinError::NotInitialized),
         }
     }
 
@@ -149,6 +188,7 @@ impl GuildPinContract {
             .set_signer(signer)
             .call()
             .await
+            .map_err(GuildPinError::from)
     }
 
     pub async fn signer(&self) -> Result<EvmAddress> {
@@ -168,6 +208,63 @@ impl GuildPinContract {
             .set_treasury(treasury)
             .call()
             .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Sends assets held by the contract to the given recipient.
+    pub async fn withdraw(
+        &self,
+        caller: &WalletUnlocked,
+        asset_id: AssetId,
+        amount: u64,
+        to: Identity,
+    ) -> Result<FuelCallResponse<()>> {
+        let contract_ids = match to {
+            Identity::ContractId(id) => vec![id.into()],
+            Identity::Address(_) => Vec::new(),
+        };
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .withdraw(asset_id, amount, to)
+            .with_contract_ids(&contract_ids)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Marks a pin revoked, e.g. when its owner left the guild or lost the respective role.
+    ///
+    /// The pin stays with its owner, but it's removed from `pin_id_by_address` and
+    /// `pin_id_by_user_id`, so the user may claim it again later.
+    pub async fn revoke(
+        &self,
+        caller: &WalletUnlocked,
+        pin_id: u64,
+        reason: RevocationReason,
+    ) -> Result<FuelCallResponse<()>> {
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .revoke(pin_id, reason)
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the non-zero balances of every asset held by the contract.
+    pub async fn contract_balances(&self) -> Result<HashMap<AssetId, u64>> {
+        let mut balances = self
+            .0
+            .account()
+            .try_provider()?
+            .get_contract_balances(self.bech_contract_id())
+            .await?;
+        balances.retain(|_, amount| *amount > 0);
+        Ok(balances)
     }
 
     pub async fn treasury(&self) -> Result<Identity> {
@@ -182,36 +279,157 @@ impl GuildPinContract {
             .set_fee(fee)
             .call()
             .await
+            .map_err(GuildPinError::from)
     }
 
+    /// Returns the global fee paid in the base asset.
+    ///
+    /// Fails with [`GuildPinError::AssetNotAccepted`] if the base asset was removed from the
+    /// accepted payment assets.
     pub async fn fee(&self) -> Result<u64> {
         Ok(self.0.methods().fee().simulate().await?.value)
     }
 
+    pub async fn set_payment_asset(
+        &self,
+        caller: &WalletUnlocked,
+        asset_id: AssetId,
+        fee: u64,
+    ) -> Result<FuelCallResponse<()>> {
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .set_payment_asset(asset_id, fee)
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    pub async fn remove_payment_asset(
+        &self,
+        caller: &WalletUnlocked,
+        asset_id: AssetId,
+    ) -> Result<FuelCallResponse<()>> {
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .remove_payment_asset(asset_id)
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the fee of an accepted payment asset, `None` if the asset is not accepted.
+    pub async fn payment_fee(&self, asset_id: AssetId) -> Result<Option<u64>> {
+        Ok(self
+            .0
+            .methods()
+            .payment_fee(asset_id)
+            .simulate()
+            .await?
+            .value)
+    }
+
+    pub async fn payment_assets(&self) -> Result<Vec<AssetId>> {
+        Ok(self.0.methods().payment_assets().simulate().await?.value)
+    }
+
+    /// Overrides the global fee of claims in a guild, of an action, or of an action in a guild.
+    ///
+    /// Passing `None` as the fee removes the override. At least one of `guild_id` and `action`
+    /// has to be set, otherwise the call reverts with [`GuildPinError::MissingFeeScope`].
+    /// Overrides are set per kind of action, so the role id of [`GuildAction::Role`] is ignored
+    /// and the override applies to every role.
+    pub async fn set_fee_override(
+        &self,
+        caller: &WalletUnlocked,
+        guild_id: Option<u64>,
+        action: Option<GuildAction>,
+        fee: Option<u64>,
+    ) -> Result<FuelCallResponse<()>> {
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .set_fee_override(guild_id, action, fee)
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the override set for exactly the given scope, if any.
+    pub async fn fee_override(
+        &self,
+        guild_id: Option<u64>,
+        action: Option<GuildAction>,
+    ) -> Result<Option<u64>> {
+        Ok(self
+            .0
+            .methods()
+            .fee_override(guild_id, action)
+            .simulate()
+            .await?
+            .value)
+    }
+
+    /// Returns the fee of a claim paid in the base asset.
+    ///
+    /// The override of the action in the guild takes precedence over that of the guild, which
+    /// takes precedence over that of the action, falling back to the global [`Self::fee`].
+    ///
+    /// Overrides are priced in the base asset. Claims covered by an override can only be paid in
+    /// the base asset, paying in any other asset reverts with
+    /// [`GuildPinError::BaseAssetRequired`]. An override applies even if the base asset has been
+    /// removed from the payment assets.
+    pub async fn fee_for(&self, guild_id: u64, action: GuildAction) -> Result<u64> {
+        Ok(self
+            .0
+            .methods()
+            .fee_for(guild_id, action)
+            .simulate()
+            .await?
+            .value)
+    }
+
+    /// Claims a pin paying with the base asset of the network the caller is connected to.
+    ///
+    /// The response's value is the amount refunded in case of an overpayment.
     pub async fn claim(
         &self,
         caller: &WalletUnlocked,
         params: ClaimParameters,
         signature: B512,
-    ) -> Result<FuelCallResponse<()>> {
-        let total_fee = self.fee().await? + params.admin_fee;
-        let asset_id = AssetId::BASE;
-        self.unsafe_claim(caller, params, signature, total_fee, asset_id)
+    ) -> Result<FuelCallResponse<u64>> {
+        let asset_id = *caller.try_provider()?.base_asset_id();
+        self.claim_with_asset(caller, params, signature, asset_id)
             .await
     }
 
-    pub async fn claim_eth(
+    /// Claims a pin paying with any of the accepted payment assets.
+    pub async fn claim_with_asset(
         &self,
         caller: &WalletUnlocked,
         params: ClaimParameters,
         signature: B512,
-    ) -> Result<FuelCallResponse<()>> {
-        let total_fee = self.fee().await? + params.admin_fee;
-        let asset_id = crate::ETHER_ASSET_ID;
+        asset_id: AssetId,
+    ) -> Result<FuelCallResponse<u64>> {
+        // fee overrides are priced in the base asset, other assets are rejected by the contract
+        // if an override applies
+        let fee = if asset_id == *caller.try_provider()?.base_asset_id() {
+            self.fee_for(params.guild_id, params.action.clone()).await?
+        } else {
+            self.payment_fee(asset_id)
+                .await?
+                .ok_or(GuildPinError::AssetNotAccepted)?
+        };
+        let total_fee = fee + params.admin_fee;
         self.unsafe_claim(caller, params, signature, total_fee, asset_id)
             .await
     }
 
+    /// Claims a pin sending exactly `total_fee` of the given asset, any surplus is refunded.
     pub async fn unsafe_claim(
         &self,
         caller: &WalletUnlocked,
@@ -219,13 +437,15 @@ impl GuildPinContract {
         signature: B512,
         total_fee: u64,
         asset_id: AssetId,
-    ) -> Result<FuelCallResponse<()>> {
+    ) -> Result<FuelCallResponse<u64>> {
+        let admin_treasury_contracts = self.admin_treasury_contracts(&params);
         self.0
             .clone()
             .with_account(caller.clone())
             .methods()
             .claim(params, signature)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(2)) // needed for sending tokens to optionally 2 treasuries
+            .with_contract_ids(&admin_treasury_contracts)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(4)) // needed for minting, sending tokens to optionally 2 treasuries and refunding
             .call_params(
                 CallParameters::default()
                     .with_asset_id(asset_id)
@@ -233,18 +453,248 @@ impl GuildPinContract {
             )?
             .call()
             .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Contracts receiving the admin fee need to be part of the transaction's inputs.
+    fn admin_treasury_contracts(&self, params: &ClaimParameters) -> Vec<Bech32ContractId> {
+        match params.admin_treasury {
+            Identity::ContractId(id) if params.admin_fee > 0 && id != self.contract_id() => {
+                vec![id.into()]
+            }
+            _ => Vec::new(),
+        }
     }
 
+    /// Checks whether a claim would succeed without submitting a transaction.
+    ///
+    /// Every check performed by the contract is done either locally or via read-only simulation,
+    /// and the caller's base asset balance is compared against the required fees.
+    pub async fn preflight_claim(
+        &self,
+        caller: &WalletUnlocked,
+        params: &ClaimParameters,
+        signature: B512,
+    ) -> Result<ClaimPreflight> {
+        let initialized = match self.owner().await {
+            Ok(_) => true,
+            Err(GuildPinError::NotInitialized) => false,
+            Err(error) => return Err(error),
+        };
+        let provider = caller.try_provider()?;
+        let timestamp = provider
+            .latest_block_time()
+            .await?
+            .map(|time| time.timestamp() as u64)
+            .unwrap_or_default();
+        let signature = verify_claim_signature(
+            params,
+            signature,
+            self.signer().await?,
+            self.contract_id(),
+            *provider.chain_id(),
+            timestamp,
+            SIGNATURE_VALIDITY_PERIOD,
+        );
+        let consumed = self.claim_consumed(params).await?;
+        let claimed_by_address = self
+            .pin_id_by_address(params.recipient, params.guild_id, params.action.clone())
+            .await?;
+        let claimed_by_user_id = self
+            .pin_id_by_user_id(params.user_id, params.guild_id, params.action.clone())
+            .await?;
+        let required_amount = if initialized {
+            match self.fee_for(params.guild_id, params.action.clone()).await {
+                Ok(fee) => Some(fee + params.admin_fee),
+                Err(GuildPinError::AssetNotAccepted) => None,
+                Err(error) => return Err(error),
+            }
+        } else {
+            None
+        };
+        let balance = provider
+            .get_asset_balance(caller.address(), *provider.base_asset_id())
+            .await?;
+
+        Ok(ClaimPreflight {
+            initialized,
+            signature,
+            consumed,
+            claimed_by_address,
+            claimed_by_user_id,
+            required_amount,
+            balance,
+        })
+    }
+
+    /// Submits multiple signed claims in a single multicall transaction.
+    ///
+    /// Every claim is simulated on its own first and the ones that would revert, or whose fee
+    /// can't be determined, are left out of the transaction. Note that claims conflicting with each other within the same batch (e.g.
+    /// the same user claiming the same pin twice) are not detected by the individual simulations.
+    pub async fn claim_batch(
+        &self,
+        caller: &WalletUnlocked,
+        claims: Vec<(ClaimParameters, B512)>,
+    ) -> Result<BatchClaimResponse> {
+        let base_asset_id = *caller.try_provider()?.base_asset_id();
+        let contract = self.0.clone().with_account(caller.clone());
+        let claim_call = |params: ClaimParameters, signature: B512, fee: u64| {
+            let call_params = CallParameters::default()
+                .with_asset_id(base_asset_id)
+                .with_amount(fee + params.admin_fee);
+            contract
+                .methods()
+                .claim(params.clone(), signature)
+                .with_contract_ids(&self.admin_treasury_contracts(&params))
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(4))
+                .call_params(call_params)
+        };
+
+        let mut multi_call = MultiContractCallHandler::new(caller.clone());
+        let mut included = 0;
+        let mut failed = Vec::new();
+        for (index, (params, signature)) in claims.into_iter().enumerate() {
+            // a failure only affects its own claim, e.g. when the fee can't be determined
+            let call = async {
+                let fee = self.fee_for(params.guild_id, params.action.clone()).await?;
+                claim_call(params.clone(), signature, fee)?
+                    .simulate()
+                    .await?;
+                Ok::<_, GuildPinError>(claim_call(params, signature, fee)?)
+            };
+            match call.await {
+                Ok(call) => {
+                    multi_call = multi_call.add_call(call);
+                    included += 1;
+                }
+                Err(error) => failed.push((index, error)),
+            }
+        }
+
+        if included == 0 {
+            return Ok(BatchClaimResponse {
+                tx_id: None,
+                receipts: Vec::new(),
+                failed,
+            });
+        }
+
+        // minting, sending the fees to the treasuries and refunding requires four variable outputs
+        // per claim
+        let tx = multi_call
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(4 * included))
+            .build_tx()
+            .await?;
+        let provider = caller.try_provider()?;
+        let tx_id = tx.id(provider.chain_id());
+        let receipts = provider
+            .send_transaction_and_await_commit(tx)
+            .await?
+            .take_receipts_checked(Some(&self.0.log_decoder()))?;
+
+        Ok(BatchClaimResponse {
+            tx_id: Some(tx_id),
+            receipts,
+            failed,
+        })
+    }
+
+    /// Replaces the guild name and artwork of a pin, authorized by a backend signature.
+    ///
+    /// Fails with [`GuildPinError::StaleUpdate`] if the update was signed before the last one
+    /// applied to the pin.
+    pub async fn update_pin_metadata(
+        &self,
+        caller: &WalletUnlocked,
+        params: UpdateParameters,
+        signature: B512,
+    ) -> Result<FuelCallResponse<()>> {
+        self.0
+            .clone()
+            .with_account(caller.clone())
+            .methods()
+            .update_pin_metadata(params, signature)
+            .call()
+            .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Mints an airdropped pin via SRC-3, only the owner is allowed to do so.
+    ///
+    /// Airdropped pins are not bound to any guild, so they can't be looked up via
+    /// `pin_id_by_address` or `pin_id_by_user_id`. Their metadata holds placeholder guild data,
+    /// i.e. guild id 0, a blank guild name and cid, and the [`GuildAction::Joined`] action.
+    ///
+    /// The contract only accepts the sub id of the next pin, which is derived from
+    /// `total_minted`. If another pin gets minted between reading it and the call, the call
+    /// reverts with [`GuildPinError::InvalidSubId`] and it is retried with a fresh sub id, at
+    /// most [`MINT_ATTEMPTS`] times.
+    pub async fn mint(
+        &self,
+        caller: &WalletUnlocked,
+        recipient: Address,
+    ) -> Result<FuelCallResponse<()>> {
+        let mut attempts = 1;
+        loop {
+            let sub_id = pin_sub_id(self.total_minted().await?);
+            let result = self
+                .0
+                .clone()
+                .with_account(caller.clone())
+                .methods()
+                .mint(Identity::Address(recipient), sub_id, 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .map_err(GuildPinError::from);
+            match result {
+                Err(GuildPinError::InvalidSubId) if attempts < MINT_ATTEMPTS => attempts += 1,
+                result => return result,
+            }
+        }
+    }
+
+    /// Burns a pin via SRC-3, sending the pin token along with the call.
+    ///
+    /// Whoever holds the pin token may burn it, even if the pin was transferred away from its
+    /// recorded owner. In soulbound mode the contract already holds the pin token, so nothing is
+    /// sent and only the recorded owner may burn it.
     pub async fn burn(&self, caller: &WalletUnlocked, pin_id: u64) -> Result<FuelCallResponse<()>> {
+        let call_params = if self.soulbound().await? {
+            CallParameters::default()
+        } else {
+            CallParameters::default()
+                .with_asset_id(self.asset_id(pin_id))
+                .with_amount(1)
+        };
         self.0
             .clone()
             .with_account(caller.clone())
             .methods()
-            .burn(pin_id)
+            .burn(pin_sub_id(pin_id), 1)
+            .call_params(call_params)?
             .call()
             .await
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns `true` if the contract was deployed in soulbound mode.
+    pub async fn soulbound(&self) -> Result<bool> {
+        self.0
+            .methods()
+            .soulbound()
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
+    /// Returns the number of pins recorded for the address in storage.
+    ///
+    /// In soulbound mode this is authoritative, the pins' assets are held by the contract itself.
+    /// Otherwise pin assets may have been transferred since they were minted, so the holder's
+    /// native balances may differ, see [`reconcile`](crate::reconcile::reconcile).
     pub async fn balance_of(&self, id: Address) -> Result<u64> {
         self.0
             .methods()
@@ -252,6 +702,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn pin_owner(&self, pin_id: u64) -> Result<Option<Address>> {
@@ -261,6 +712,20 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the owners of at most `count` pins starting from `start`, in pin id order.
+    ///
+    /// Burned pins have no owner, and the result is cut short at the last minted pin.
+    pub async fn pin_owners(&self, start: u64, count: u64) -> Result<Vec<Option<Address>>> {
+        self.0
+            .methods()
+            .pin_owners(start, count)
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn total_minted(&self) -> Result<u64> {
@@ -270,6 +735,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn total_minted_per_guild(&self, guild_id: u64) -> Result<u64> {
@@ -279,6 +745,31 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the cumulative admin fees forwarded to the admin treasuries of a guild in the given
+    /// payment asset.
+    pub async fn admin_fees_per_guild(&self, guild_id: u64, asset_id: AssetId) -> Result<u64> {
+        self.0
+            .methods()
+            .admin_fees_per_guild(guild_id, asset_id)
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns `true` if the signed claim has already been used to mint a pin.
+    pub async fn claim_consumed(&self, params: &ClaimParameters) -> Result<bool> {
+        let message = Bits256(claim_message(params));
+        self.0
+            .methods()
+            .message_consumed(message)
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn pin_id_by_address(
@@ -293,6 +784,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn pin_id_by_user_id(
@@ -307,6 +799,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn pin_metadata(&self, pin_id: u64) -> Result<String> {
@@ -316,6 +809,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn token_of_owner_by_index(
@@ -329,6 +823,7 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
     pub async fn encoded_metadata(&self, pin_id: u64) -> Result<String> {
@@ -338,22 +833,94 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the pin's metadata via SRC-7, `None` if the pin doesn't exist or has been burned.
+    pub async fn metadata(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "metadata", as_string).await
+    }
+
+    /// Returns the pin's `name` SRC-9 metadata, i.e. the guild action.
+    pub async fn metadata_name(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "name", as_string).await
+    }
+
+    /// Returns the pin's `description` SRC-9 metadata.
+    pub async fn metadata_description(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "description", as_string).await
+    }
+
+    /// Returns the pin's `image` SRC-9 metadata, i.e. the ipfs uri of the pin's artwork.
+    pub async fn metadata_image(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "image", as_string).await
+    }
+
+    /// Returns the pin's `type` attribute.
+    pub async fn metadata_type(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "type", as_string).await
+    }
+
+    /// Returns the pin's `guildId` attribute.
+    pub async fn metadata_guild_id(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "guildId", as_int).await
+    }
+
+    /// Returns the pin's `roleId` attribute, which only role pins have.
+    pub async fn metadata_role_id(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "roleId", as_int).await
+    }
+
+    /// Returns the pin's `userId` attribute.
+    pub async fn metadata_user_id(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "userId", as_int).await
+    }
+
+    /// Returns the pin's `rank` attribute, which is the pin id itself.
+    pub async fn metadata_rank(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "rank", as_int).await
+    }
+
+    /// Returns the pin's `mintDate` attribute as a unix timestamp.
+    pub async fn metadata_mint_date(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "mintDate", as_int).await
+    }
+
+    /// Returns the pin's `actionDate` attribute as a unix timestamp.
+    pub async fn metadata_action_date(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.metadata_as(pin_id, "actionDate", as_int).await
+    }
+
+    /// Returns the pin's `status` attribute, i.e. `active` or `revoked`.
+    pub async fn metadata_status(&self, pin_id: u64) -> Result<Option<String>> {
+        self.metadata_as(pin_id, "status", as_string).await
     }
 
-    pub async fn metadata(&self, asset_id: AssetId, pin_id: String) -> Result<String> {
-        let maybe_metadata = self
+    /// Fetches a pin's SRC-7 metadata under `key` and extracts its value, a value of an
+    /// unexpected variant is an error.
+    async fn metadata_as<T>(
+        &self,
+        pin_id: u64,
+        key: &str,
+        extract: fn(Metadata) -> Option<T>,
+    ) -> Result<Option<T>> {
+        let metadata = self
             .0
             .methods()
-            .metadata(asset_id, pin_id)
+            .metadata(self.asset_id(pin_id), key.to_string())
             .simulate()
             .await
-            .map(|r| r.value)?;
-
-        if let Some(Metadata::String(metadata)) = maybe_metadata {
-            Ok(metadata)
-        } else {
-            Err(Error::Other("invalid metadata".to_string()))
-        }
+            .map_err(GuildPinError::from)?
+            .value;
+        metadata
+            .map(|metadata| {
+                extract(metadata).ok_or_else(|| {
+                    GuildPinError::Fuels(fuels::types::errors::Error::Other(
+                        "invalid metadata".to_string(),
+                    ))
+                })
+            })
+            .transpose()
     }
 
     pub async fn total_assets(&self) -> Result<u64> {
@@ -363,41 +930,72 @@ impl GuildPinContract {
             .simulate()
             .await
             .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
+    /// Returns the number of pins in circulation, i.e. minted and not burned.
     pub async fn total_supply(&self) -> Result<u64> {
         self.0
             .methods()
-            .total_supply(self.asset_id())
+            .circulating_supply()
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+
+    /// Returns the SRC-20 supply of a pin's asset, `None` if the pin doesn't exist.
+    pub async fn asset_supply(&self, pin_id: u64) -> Result<Option<u64>> {
+        self.0
+            .methods()
+            .total_supply(self.asset_id(pin_id))
             .simulate()
             .await
-            .map(|r| r.value.unwrap())
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
-    pub async fn name(&self) -> Result<String> {
+    pub async fn name(&self, pin_id: u64) -> Result<Option<String>> {
         self.0
             .methods()
-            .name(self.asset_id())
+            .name(self.asset_id(pin_id))
             .simulate()
             .await
-            .map(|r| r.value.unwrap())
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
-    pub async fn symbol(&self) -> Result<String> {
+    pub async fn symbol(&self, pin_id: u64) -> Result<Option<String>> {
         self.0
             .methods()
-            .symbol(self.asset_id())
+            .symbol(self.asset_id(pin_id))
             .simulate()
             .await
-            .map(|r| r.value.unwrap())
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
     }
 
-    pub async fn decimals(&self) -> Result<u8> {
+    pub async fn decimals(&self, pin_id: u64) -> Result<Option<u8>> {
         self.0
             .methods()
-            .decimals(self.asset_id())
+            .decimals(self.asset_id(pin_id))
             .simulate()
             .await
-            .map(|r| r.value.unwrap())
+            .map(|r| r.value)
+            .map_err(GuildPinError::from)
+    }
+}
+
+fn as_string(metadata: Metadata) -> Option<String> {
+    match metadata {
+        Metadata::String(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn as_int(metadata: Metadata) -> Option<u64> {
+    match metadata {
+        Metadata::Int(value) => Some(value),
+        _ => None,
     }
 }
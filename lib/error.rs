@@ -0,0 +1,235 @@
+use fuels::types::errors::transaction::Reason;
+use fuels::types::errors::Error;
+
+use std::fmt;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, GuildPinError>;
+
+/// Typed representation of the errors the contract may revert with.
+///
+/// Reverts are matched on the reason string the SDK decodes from the contract's `require` logs,
+/// every other error is passed through as [`GuildPinError::Fuels`].
+#[derive(Debug)]
+pub enum GuildPinError {
+    // InitError
+    AlreadyInitialized,
+    NotInitialized,
+    // sway_libs ownership error
+    NotOwner,
+    // OwnerError
+    InsufficientBalance,
+    CannotWithdrawPin,
+    // TokenError
+    AlreadyClaimed,
+    AlreadyBurned,
+    ExpiredSignature,
+    InvalidSignature,
+    InvalidAssetId,
+    InvalidContractId,
+    InvalidChainId,
+    MessageAlreadyConsumed,
+    InsufficientAmount,
+    PinIdDoesNotExist,
+    NotPinOwner,
+    CouldNotRemoveEntry,
+    InvalidAmount,
+    InvalidSubId,
+    InvalidRecipient,
+    AlreadyRevoked,
+    StaleUpdate,
+    // PaymentError
+    AssetNotAccepted,
+    // FeeError
+    MissingFeeScope,
+    BaseAssetRequired,
+    /// Any other error returned by the sdk
+    Fuels(Error),
+}
+
+impl GuildPinError {
+    /// Maps a revert reason to the respective contract error.
+    pub fn from_reason(reason: &str) -> Option<Self> {
+        let error = match reason {
+            "AlreadyInitialized" => Self::AlreadyInitialized,
+            "NotInitialized" => Self::NotInitialized,
+            "NotOwner" => Self::NotOwner,
+            "InsufficientBalance" => Self::InsufficientBalance,
+            "CannotWithdrawPin" => Self::CannotWithdrawPin,
+            "AlreadyClaimed" => Self::AlreadyClaimed,
+            "AlreadyBurned" => Self::AlreadyBurned,
+            "ExpiredSignature" => Self::ExpiredSignature,
+            "InvalidSignature" => Self::InvalidSignature,
+            "InvalidAssetId" => Self::InvalidAssetId,
+            "InvalidContractId" => Self::InvalidContractId,
+            "InvalidChainId" => Self::InvalidChainId,
+            "MessageAlreadyConsumed" => Self::MessageAlreadyConsumed,
+            "InsufficientAmount" => Self::InsufficientAmount,
+            "PinIdDoesNotExist" => Self::PinIdDoesNotExist,
+            "NotPinOwner" => Self::NotPinOwner,
+            "CouldNotRemoveEntry" => Self::CouldNotRemoveEntry,
+            "InvalidAmount" => Self::InvalidAmount,
+            "InvalidSubId" => Self::InvalidSubId,
+            "InvalidRecipient" => Self::InvalidRecipient,
+            "AlreadyRevoked" => Self::AlreadyRevoked,
+            "StaleUpdate" => Self::StaleUpdate,
+            "AssetNotAccepted" => Self::AssetNotAccepted,
+            "MissingFeeScope" => Self::MissingFeeScope,
+            "BaseAssetRequired" => Self::BaseAssetRequired,
+            _ => return None,
+        };
+        Some(error)
+    }
+}
+
+impl From<Error> for GuildPinError {
+    fn from(error: Error) -> Self {
+        if let Error::Transaction(Reason::Reverted { reason, .. }) = &error {
+            if let Some(contract_error) = Self::from_reason(reason) {
+                return contract_error;
+            }
+        }
+        Self::Fuels(error)
+    }
+}
+
+impl fmt::Display for GuildPinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fuels(error) => write!(f, "{}", error),
+            // variant names are identical to the contract's revert reasons
+            contract_error => write!(f, "{:?}", contract_error),
+        }
+    }
+}
+
+impl std::error::Error for GuildPinError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fuels(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that may occur while assembling [`Parameters`](crate::parameters::Parameters).
+#[derive(Debug)]
+pub enum ParametersError {
+    /// A key file could not be read
+    Io(std::io::Error),
+    /// The signer seed file is not a json byte array
+    Json(serde_json::Error),
+    /// A secret key file doesn't contain a valid hex encoded secret key
+    InvalidSecretKey(String),
+    /// An encrypted keystore could not be decrypted
+    Keystore(String),
+    /// The environment variable holding the keystore password is not set
+    MissingPassword(String),
+    /// Could not connect to the provider
+    Provider(Error),
+    /// A role required for building the parameters was not provided
+    MissingRole(&'static str),
+}
+
+impl From<std::io::Error> for ParametersError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ParametersError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<Error> for ParametersError {
+    fn from(error: Error) -> Self {
+        Self::Provider(error)
+    }
+}
+
+impl fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read key file: {}", error),
+            Self::Json(error) => write!(f, "invalid signer seed: {}", error),
+            Self::InvalidSecretKey(error) => write!(f, "invalid secret key: {}", error),
+            Self::Keystore(error) => write!(f, "could not decrypt keystore: {}", error),
+            Self::MissingPassword(var) => write!(f, "password env var {} is not set", var),
+            Self::Provider(error) => write!(f, "could not connect to provider: {}", error),
+            Self::MissingRole(role) => write!(f, "missing {}", role),
+        }
+    }
+}
+
+impl std::error::Error for ParametersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Provider(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that may occur while indexing contract events.
+#[derive(Debug)]
+pub enum IndexerError {
+    /// The event store could not be read or written
+    Io(std::io::Error),
+    /// The event store contains invalid records
+    Json(serde_json::Error),
+    /// Querying the provider or decoding logs failed
+    Fuels(Error),
+    /// The provider doesn't know about a block below the latest height
+    MissingBlock(u32),
+    /// The events file exists without a checkpoint, so it's unclear where indexing stopped
+    MissingCheckpoint(PathBuf),
+}
+
+impl From<std::io::Error> for IndexerError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<Error> for IndexerError {
+    fn from(error: Error) -> Self {
+        Self::Fuels(error)
+    }
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "event store io error: {}", error),
+            Self::Json(error) => write!(f, "invalid event store record: {}", error),
+            Self::Fuels(error) => write!(f, "{}", error),
+            Self::MissingBlock(height) => write!(f, "block {} not found", height),
+            Self::MissingCheckpoint(path) => write!(
+                f,
+                "{} has no checkpoint, remove it or pick another file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Fuels(error) => Some(error),
+            Self::MissingBlock(_) | Self::MissingCheckpoint(_) => None,
+        }
+    }
+}
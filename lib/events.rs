@@ -0,0 +1,87 @@
+use crate::contract::{
+    ContractInitialized, FeeChanged, FeeOverrideChanged, OwnershipSet, OwnershipTransferred,
+    PaymentAssetRemoved, PaymentAssetSet, PinBurned, PinMetadataUpdated, PinMinted, PinRevoked,
+    SignerChanged, TreasuryChanged, Withdrawn,
+};
+use fuels::core::codec::LogDecoder;
+use fuels::core::traits::{Parameterize, Tokenizable};
+use fuels::prelude::Result;
+use fuels::tx::Receipt;
+
+/// Every event the contract may log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuildPinEvent {
+    ContractInitialized(ContractInitialized),
+    OwnershipSet(OwnershipSet),
+    OwnershipTransferred(OwnershipTransferred),
+    SignerChanged(SignerChanged),
+    TreasuryChanged(TreasuryChanged),
+    FeeChanged(FeeChanged),
+    FeeOverrideChanged(FeeOverrideChanged),
+    PaymentAssetSet(PaymentAssetSet),
+    PaymentAssetRemoved(PaymentAssetRemoved),
+    Withdrawn(Withdrawn),
+    PinMinted(PinMinted),
+    PinBurned(PinBurned),
+    PinRevoked(PinRevoked),
+    PinMetadataUpdated(PinMetadataUpdated),
+}
+
+/// Decodes every contract event found in the receipts, in the order they were logged.
+///
+/// Logs that are not contract events (e.g. logged strings) are skipped.
+pub fn decode_events(decoder: &LogDecoder, receipts: &[Receipt]) -> Result<Vec<GuildPinEvent>> {
+    let mut events = Vec::new();
+    for receipt in receipts {
+        if let Some(event) = decode_receipt(decoder, receipt)? {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+fn decode_receipt(decoder: &LogDecoder, receipt: &Receipt) -> Result<Option<GuildPinEvent>> {
+    if !matches!(receipt, Receipt::LogData { .. }) {
+        return Ok(None);
+    }
+    let receipts = std::slice::from_ref(receipt);
+    // a receipt holds a single log, so the first matching type wins and the rest are skipped
+    macro_rules! decode {
+        ($($variant:ident),*) => {
+            $(
+                if let Some(event) = try_decode(decoder, receipts, GuildPinEvent::$variant)? {
+                    return Ok(Some(event));
+                }
+            )*
+        };
+    }
+    decode!(
+        ContractInitialized,
+        OwnershipSet,
+        OwnershipTransferred,
+        SignerChanged,
+        TreasuryChanged,
+        FeeChanged,
+        FeeOverrideChanged,
+        PaymentAssetSet,
+        PaymentAssetRemoved,
+        Withdrawn,
+        PinMinted,
+        PinBurned,
+        PinRevoked,
+        PinMetadataUpdated
+    );
+    Ok(None)
+}
+
+fn try_decode<T>(
+    decoder: &LogDecoder,
+    receipts: &[Receipt],
+    wrap: fn(T) -> GuildPinEvent,
+) -> Result<Option<GuildPinEvent>>
+where
+    T: 'static + Parameterize + Tokenizable,
+{
+    let decoded = decoder.decode_logs_with_type::<T>(receipts)?;
+    Ok(decoded.into_iter().next().map(wrap))
+}
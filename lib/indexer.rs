@@ -0,0 +1,368 @@
+use crate::contract::GuildPinContract;
+use crate::error::IndexerError;
+use crate::events::{decode_events, GuildPinEvent};
+use fuels::accounts::provider::Provider;
+use fuels::core::codec::LogDecoder;
+use fuels::tx::Receipt;
+use fuels::types::tx_status::TxStatus;
+use fuels::types::{Address, AssetId, ContractId, EvmAddress, Identity};
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Contract event in a serializable form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Event {
+    PinMinted {
+        recipient: Address,
+        pin_id: u64,
+    },
+    PinBurned {
+        pin_owner: Address,
+        pin_id: u64,
+    },
+    PinRevoked {
+        pin_owner: Address,
+        pin_id: u64,
+        /// Name of the contract's `RevocationReason` variant
+        reason: String,
+    },
+    PinMetadataUpdated {
+        pin_id: u64,
+        guild_name: String,
+        cid: String,
+    },
+    ContractInitialized {
+        owner: Identity,
+        signer: EvmAddress,
+        treasury: Identity,
+        fee: u64,
+    },
+    FeeChanged {
+        old: u64,
+        new: u64,
+    },
+    FeeOverrideChanged {
+        guild_id: Option<u64>,
+        /// Debug representation of the contract's `GuildAction`, e.g. `Role(42)`
+        action: Option<String>,
+        old: Option<u64>,
+        new: Option<u64>,
+    },
+    PaymentAssetSet {
+        asset: AssetId,
+        old_fee: Option<u64>,
+        new_fee: u64,
+    },
+    PaymentAssetRemoved {
+        asset: AssetId,
+        fee: u64,
+    },
+    Withdrawn {
+        asset: AssetId,
+        amount: u64,
+        to: Identity,
+    },
+    SignerChanged {
+        old: EvmAddress,
+        new: EvmAddress,
+    },
+    TreasuryChanged {
+        old: Identity,
+        new: Identity,
+    },
+    OwnershipSet {
+        new_owner: Identity,
+    },
+    OwnershipTransferred {
+        previous_owner: Identity,
+        new_owner: Identity,
+    },
+}
+
+impl From<GuildPinEvent> for Event {
+    fn from(event: GuildPinEvent) -> Self {
+        match event {
+            GuildPinEvent::PinMinted(e) => Self::PinMinted {
+                recipient: e.recipient,
+                pin_id: e.pin_id,
+            },
+            GuildPinEvent::PinBurned(e) => Self::PinBurned {
+                pin_owner: e.pin_owner,
+                pin_id: e.pin_id,
+            },
+            GuildPinEvent::PinRevoked(e) => Self::PinRevoked {
+                pin_owner: e.pin_owner,
+                pin_id: e.pin_id,
+                reason: format!("{:?}", e.reason),
+            },
+            GuildPinEvent::PinMetadataUpdated(e) => Self::PinMetadataUpdated {
+                pin_id: e.pin_id,
+                guild_name: e.guild_name.to_trimmed_str().to_string(),
+                cid: e.cid.to_trimmed_str().to_string(),
+            },
+            GuildPinEvent::ContractInitialized(e) => Self::ContractInitialized {
+                owner: e.owner,
+                signer: e.signer,
+                treasury: e.treasury,
+                fee: e.fee,
+            },
+            GuildPinEvent::FeeChanged(e) => Self::FeeChanged {
+                old: e.old,
+                new: e.new,
+            },
+            GuildPinEvent::FeeOverrideChanged(e) => Self::FeeOverrideChanged {
+                guild_id: e.guild_id,
+                action: e.action.map(|action| format!("{:?}", action)),
+                old: e.old,
+                new: e.new,
+            },
+            GuildPinEvent::PaymentAssetSet(e) => Self::PaymentAssetSet {
+                asset: e.asset,
+                old_fee: e.old_fee,
+                new_fee: e.new_fee,
+            },
+            GuildPinEvent::PaymentAssetRemoved(e) => Self::PaymentAssetRemoved {
+                asset: e.asset,
+                fee: e.fee,
+            },
+            GuildPinEvent::Withdrawn(e) => Self::Withdrawn {
+                asset: e.asset,
+                amount: e.amount,
+                to: e.to,
+            },
+            GuildPinEvent::SignerChanged(e) => Self::SignerChanged {
+                old: e.old,
+                new: e.new,
+            },
+            GuildPinEvent::TreasuryChanged(e) => Self::TreasuryChanged {
+                old: e.old,
+                new: e.new,
+            },
+            GuildPinEvent::OwnershipSet(e) => Self::OwnershipSet {
+                new_owner: e.new_owner,
+            },
+            GuildPinEvent::OwnershipTransferred(e) => Self::OwnershipTransferred {
+                previous_owner: e.previous_owner,
+                new_owner: e.new_owner,
+            },
+        }
+    }
+}
+
+/// An event along with the block and transaction that emitted it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub block_height: u32,
+    pub tx_id: String,
+    pub event: Event,
+}
+
+/// Append-only json-lines event store with a checkpoint file next to it.
+///
+/// The checkpoint holds the next block height to index. It is only updated after all events of a
+/// block have been flushed, so records beyond the checkpoint are leftovers of an interrupted run
+/// and they are dropped upon opening the store.
+pub struct JsonLinesStore {
+    events_path: PathBuf,
+    checkpoint_path: PathBuf,
+}
+
+impl JsonLinesStore {
+    /// Opens the store at `events_path`, which is created upon the first commit.
+    ///
+    /// An existing events file without a checkpoint is never touched, opening it fails with
+    /// [`IndexerError::MissingCheckpoint`] instead.
+    pub fn open(events_path: impl AsRef<Path>) -> Result<Self, IndexerError> {
+        let events_path = events_path.as_ref().to_path_buf();
+        let checkpoint_path = events_path.with_extension("checkpoint");
+        let store = Self {
+            events_path,
+            checkpoint_path,
+        };
+
+        if let Some(checkpoint) = store.checkpoint()? {
+            let records = store.records()?;
+            if records.iter().any(|r| r.block_height >= checkpoint) {
+                let records = records.into_iter().filter(|r| r.block_height < checkpoint);
+                let mut file = File::create(&store.events_path)?;
+                for record in records {
+                    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+                }
+                file.sync_all()?;
+            }
+        } else if store.events_path.exists() {
+            // events without a checkpoint are not trustworthy, but they might not even be ours
+            return Err(IndexerError::MissingCheckpoint(store.events_path));
+        }
+
+        Ok(store)
+    }
+
+    /// Returns the next block height to index, `None` if nothing was indexed yet.
+    pub fn checkpoint(&self) -> Result<Option<u32>, IndexerError> {
+        if !self.checkpoint_path.exists() {
+            return Ok(None);
+        }
+        let checkpoint = std::fs::read_to_string(&self.checkpoint_path)?;
+        Ok(Some(serde_json::from_str(&checkpoint)?))
+    }
+
+    /// Persists the events of a block and moves the checkpoint past it.
+    pub fn commit(&self, block_height: u32, records: &[Record]) -> Result<(), IndexerError> {
+        if !records.is_empty() {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.events_path)?;
+            for record in records {
+                writeln!(file, "{}", serde_json::to_string(record)?)?;
+            }
+            file.sync_all()?;
+        }
+
+        // write-then-rename, so the checkpoint is never half-written
+        let tmp_path = self.checkpoint_path.with_extension("checkpoint.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string(&(block_height + 1))?)?;
+        std::fs::rename(tmp_path, &self.checkpoint_path)?;
+        Ok(())
+    }
+
+    pub fn records(&self) -> Result<Vec<Record>, IndexerError> {
+        if !self.events_path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(File::open(&self.events_path)?);
+        let mut records = Vec::new();
+        for line in reader.lines() {
+            records.push(serde_json::from_str(&line?)?);
+        }
+        Ok(records)
+    }
+
+    /// Pin ids currently held by each address, in minting order.
+    pub fn pins_by_owner(&self) -> Result<BTreeMap<Address, Vec<u64>>, IndexerError> {
+        let mut pins = BTreeMap::<Address, Vec<u64>>::new();
+        for record in self.records()? {
+            match record.event {
+                Event::PinMinted { recipient, pin_id } => {
+                    pins.entry(recipient).or_default().push(pin_id)
+                }
+                Event::PinBurned { pin_owner, pin_id } => {
+                    if let Some(owned) = pins.get_mut(&pin_owner) {
+                        owned.retain(|id| *id != pin_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        pins.retain(|_, owned| !owned.is_empty());
+        Ok(pins)
+    }
+
+    pub fn pins_of(&self, owner: Address) -> Result<Vec<u64>, IndexerError> {
+        Ok(self.pins_by_owner()?.remove(&owner).unwrap_or_default())
+    }
+}
+
+/// Walks the chain block by block and persists the events emitted by the contract.
+pub struct Indexer {
+    provider: Provider,
+    contract_id: ContractId,
+    decoder: LogDecoder,
+    store: JsonLinesStore,
+    start_height: u32,
+}
+
+impl Indexer {
+    /// Creates an indexer that starts from `start_height`, unless the store already has a
+    /// checkpoint, in which case it resumes from there.
+    pub fn new(
+        contract: &GuildPinContract,
+        provider: Provider,
+        store: JsonLinesStore,
+        start_height: u32,
+    ) -> Self {
+        Self {
+            provider,
+            contract_id: contract.contract_id(),
+            decoder: contract.inner().log_decoder(),
+            store,
+            start_height,
+        }
+    }
+
+    pub fn store(&self) -> &JsonLinesStore {
+        &self.store
+    }
+
+    /// Indexes every block up to the latest one and returns the next height to index.
+    pub async fn sync(&self) -> Result<u32, IndexerError> {
+        let mut height = self.store.checkpoint()?.unwrap_or(self.start_height);
+        let latest_height = self.provider.latest_block_height().await?;
+        while height <= latest_height {
+            self.index_block(height).await?;
+            height += 1;
+        }
+        Ok(height)
+    }
+
+    async fn index_block(&self, height: u32) -> Result<(), IndexerError> {
+        let block = self
+            .provider
+            .block_by_height(height.into())
+            .await?
+            .ok_or(IndexerError::MissingBlock(height))?;
+
+        let mut records = Vec::new();
+        for tx_id in block.transactions {
+            // reverted transactions don't change the state, so their logs are ignored
+            let TxStatus::Success { receipts } = self.provider.tx_status(&tx_id).await? else {
+                continue;
+            };
+            let receipts = receipts
+                .into_iter()
+                .filter(|receipt| {
+                    matches!(receipt, Receipt::LogData { id, .. } if *id == self.contract_id)
+                })
+                .collect::<Vec<Receipt>>();
+            if receipts.is_empty() {
+                continue;
+            }
+
+            let tx_id = format!("{:#x}", tx_id);
+            for event in decode_events(&self.decoder, &receipts)? {
+                records.push(Record {
+                    block_height: height,
+                    tx_id: tx_id.clone(),
+                    event: event.into(),
+                });
+            }
+        }
+
+        self.store.commit(height, &records)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn events_without_checkpoint_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let events_path = dir.path().join("pins.jsonl");
+        std::fs::write(&events_path, "not an event\n").unwrap();
+
+        let error = JsonLinesStore::open(&events_path).err().unwrap();
+        assert!(matches!(error, IndexerError::MissingCheckpoint(path) if path == events_path));
+        assert_eq!(
+            std::fs::read_to_string(&events_path).unwrap(),
+            "not an event\n"
+        );
+    }
+}
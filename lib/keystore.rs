@@ -0,0 +1,149 @@
+use crate::error::ParametersError;
+use fuels::crypto::SecretKey;
+
+use std::path::Path;
+
+/// Derivation path of forc-wallet accounts, the account index is substituted for `{}`.
+const FORC_WALLET_DERIVATION_PATH: &str = "m/44'/1179993420'/{}'/0/0";
+
+/// Source of the password that decrypts a keystore.
+#[derive(Clone, Debug)]
+pub enum Password {
+    /// Read the password from the given environment variable.
+    Env(String),
+    /// Prompt for the password on the terminal without echoing it.
+    Prompt,
+    /// Use the given password, e.g. one fetched from a secret manager.
+    Plain(String),
+}
+
+impl Password {
+    /// Returns the password, `name` is only used to tell the user which key is being decrypted.
+    pub fn read(&self, name: &str) -> Result<String, ParametersError> {
+        match self {
+            Self::Env(var) => {
+                std::env::var(var).map_err(|_| ParametersError::MissingPassword(var.clone()))
+            }
+            Self::Prompt => Ok(rpassword::prompt_password(format!(
+                "password for {}: ",
+                name
+            ))?),
+            Self::Plain(password) => Ok(password.clone()),
+        }
+    }
+}
+
+/// Decrypts an Ethereum-style encrypted JSON keystore (scrypt or pbkdf2 + aes-128-ctr).
+pub fn decrypt(
+    path: impl AsRef<Path>,
+    password: &Password,
+    name: &str,
+) -> Result<Vec<u8>, ParametersError> {
+    let password = password.read(name)?;
+    eth_keystore::decrypt_key(path, password).map_err(|e| ParametersError::Keystore(e.to_string()))
+}
+
+/// Decrypts a fuel secret key from an encrypted JSON keystore.
+pub fn decrypt_secret_key(
+    path: impl AsRef<Path>,
+    password: &Password,
+    name: &str,
+) -> Result<SecretKey, ParametersError> {
+    let bytes = decrypt(path, password, name)?;
+    SecretKey::try_from(bytes.as_slice())
+        .map_err(|e| ParametersError::InvalidSecretKey(e.to_string()))
+}
+
+/// Decrypts the 32 byte seed of an EVM signer from an encrypted JSON keystore.
+pub fn decrypt_seed(
+    path: impl AsRef<Path>,
+    password: &Password,
+    name: &str,
+) -> Result<[u8; 32], ParametersError> {
+    let bytes = decrypt(path, password, name)?;
+    bytes
+        .try_into()
+        .map_err(|_| ParametersError::InvalidSecretKey("seed should be 32 bytes".to_string()))
+}
+
+/// Derives the secret key of an account stored in a forc-wallet vault.
+///
+/// The vault is an encrypted JSON keystore holding the wallet's mnemonic phrase, the default
+/// location is `~/.fuel/wallets/.wallet`.
+pub fn decrypt_forc_wallet(
+    path: impl AsRef<Path>,
+    account_index: usize,
+    password: &Password,
+    name: &str,
+) -> Result<SecretKey, ParametersError> {
+    let bytes = decrypt(path, password, name)?;
+    let phrase = String::from_utf8(bytes)
+        .map_err(|_| ParametersError::Keystore("invalid mnemonic phrase".to_string()))?;
+    let derivation_path = FORC_WALLET_DERIVATION_PATH.replace("{}", &account_index.to_string());
+    SecretKey::new_from_mnemonic_phrase_with_path(&phrase, &derivation_path)
+        .map_err(|e| ParametersError::InvalidSecretKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // generated in forc-wallet's vault format, i.e. the mnemonic phrase below in an encrypted json
+    // keystore, with the password "1234"
+    const FORC_WALLET_VAULT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/forc-wallet-vault.json"
+    );
+    const FORC_WALLET_PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn keystore_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let seed = [11u8; 32];
+        let name = "signer.json";
+        eth_keystore::encrypt_key(&dir, &mut rand::thread_rng(), seed, "pass", Some(name)).unwrap();
+        let path = dir.path().join(name);
+
+        let password = Password::Plain("pass".to_string());
+        assert_eq!(decrypt_seed(&path, &password, "signer").unwrap(), seed);
+        let secret_key = decrypt_secret_key(&path, &password, "signer").unwrap();
+        assert_eq!(*secret_key, seed);
+
+        let password = Password::Env("GUILD_PIN_KEYSTORE_TEST_MISSING".to_string());
+        assert!(matches!(
+            decrypt_seed(&path, &password, "signer"),
+            Err(ParametersError::MissingPassword(_))
+        ));
+
+        let password = Password::Plain("wrong".to_string());
+        assert!(matches!(
+            decrypt_seed(&path, &password, "signer"),
+            Err(ParametersError::Keystore(_))
+        ));
+    }
+
+    #[test]
+    fn forc_wallet_accounts() {
+        let password = Password::Plain("1234".to_string());
+        let phrase = decrypt(FORC_WALLET_VAULT, &password, "vault").unwrap();
+        assert_eq!(String::from_utf8(phrase).unwrap(), FORC_WALLET_PHRASE);
+
+        // expected keys were derived independently along m/44'/1179993420'/{index}'/0/0
+        let expected = [
+            "5fb4fd8deaedcb806fc34478544bd0dae7a9020800c4c6ff9b2397875fc1a5e8",
+            "cd5073e4550aa09e64b85600c2b9312f76d2c3d6fd79f1dec43d872c49c82893",
+        ];
+        for (index, expected) in expected.into_iter().enumerate() {
+            let secret_key =
+                decrypt_forc_wallet(FORC_WALLET_VAULT, index, &password, "vault").unwrap();
+            assert_eq!(hex::encode(*secret_key), expected);
+        }
+
+        let password = Password::Plain("wrong".to_string());
+        assert!(matches!(
+            decrypt_forc_wallet(FORC_WALLET_VAULT, 0, &password, "vault"),
+            Err(ParametersError::Keystore(_))
+        ));
+    }
+}
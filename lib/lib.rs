@@ -10,6 +10,12 @@ pub const ETHER_ASSET_ID: AssetId = AssetId::new([
 ]);
 
 pub mod contract;
+pub mod error;
+pub mod events;
+pub mod indexer;
+pub mod keystore;
 pub mod metadata;
 pub mod parameters;
+pub mod reconcile;
 pub mod utils;
+pub mod verify;
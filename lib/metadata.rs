@@ -1,5 +1,11 @@
+use crate::contract::{ClaimParameters, GuildAction};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use fuels::types::{Address, SizedAsciiString};
 use serde::{Deserialize, Serialize};
 
+/// Prefix of the base64 encoded token uri returned by `encoded_metadata`.
+pub const DATA_URI_PREFIX: &str = "data:application/json;base64,";
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Action {
     Joined,
@@ -7,6 +13,8 @@ pub enum Action {
     Admin,
     #[serde(rename = "Owner of")]
     Owner,
+    #[serde(rename = "Role in")]
+    Role,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -14,7 +22,8 @@ pub struct TokenUri {
     pub name: Action,
     pub description: String,
     pub image: String,
-    pub attributes: [Attribute; 6],
+    /// Role pins have an extra `roleId` attribute after `guildId`.
+    pub attributes: Vec<Attribute>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -23,10 +32,20 @@ pub struct TokenUri {
 pub enum Attribute {
     Type(Action),
     GuildId(u64),
+    RoleId(u64),
     UserId(u64),
     Rank(u64),
     ActionDate(String), // dates are formatted as string
     MintDate(String),   // dates are formatted as string
+    Status(Status),
+}
+
+/// Revoked pins remain with their owners, but they no longer prove guild membership.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Active,
+    Revoked,
 }
 
 impl Attribute {
@@ -38,3 +57,209 @@ impl Attribute {
         }
     }
 }
+
+/// Rust counterpart of the contract's `PinData` storage struct.
+///
+/// Mirrors `src/common/pin.sw` so that the token URI of a pin can be rendered offline, yielding
+/// the exact same bytes as the contract's `pin_metadata` and `encoded_metadata` calls.
+#[derive(Clone, Debug)]
+pub struct PinData {
+    pub holder: Address,
+    pub action: GuildAction,
+    pub user_id: u64,
+    pub guild_id: u64,
+    pub guild_name: SizedAsciiString<64>,
+    pub created_at: u64,
+    pub mint_date: u64,
+    pub cid: SizedAsciiString<64>,
+    pub revoked: bool,
+}
+
+impl PinData {
+    /// Constructs the data persisted by the contract upon a successful claim.
+    ///
+    /// The `mint_date` is the unix timestamp of the block that included the claim.
+    pub fn from_claim(params: &ClaimParameters, mint_date: u64) -> Self {
+        Self {
+            holder: params.recipient,
+            action: params.action.clone(),
+            user_id: params.user_id,
+            guild_id: params.guild_id,
+            guild_name: params.guild_name.clone(),
+            created_at: params.created_at,
+            mint_date,
+            cid: params.cid.clone(),
+            revoked: false,
+        }
+    }
+
+    // NOTE the contract doesn't escape anything, it just glues the raw bytes together, so no
+    // serde here either, otherwise the output might differ
+    pub fn encode(&self, pin_id: u64) -> String {
+        let mut json = String::new();
+        json.push('{');
+        json.push_str(&format!(r#""name":"{}","#, action_str(&self.action)));
+        json.push_str(&format!(
+            r#""description":"This is an onchain proof that you{}{} on Guild.xyz","#,
+            action_description(&self.action),
+            unpad(&self.guild_name.to_string()),
+        ));
+        json.push_str(&format!(
+            r#""image":"ipfs://{}","#,
+            unpad(&self.cid.to_string())
+        ));
+        json.push_str(r#""attributes":["#);
+        json.push_str(&format!(
+            r#"{{"trait_type":"type","value":"{}"}},"#,
+            action_str(&self.action)
+        ));
+        json.push_str(&format!(
+            r#"{{"trait_type":"guildId","value":{}}},"#,
+            self.guild_id
+        ));
+        if let GuildAction::Role(role_id) = self.action {
+            json.push_str(&format!(
+                r#"{{"trait_type":"roleId","value":{}}},"#,
+                role_id
+            ));
+        }
+        json.push_str(&format!(
+            r#"{{"trait_type":"userId","value":{}}},"#,
+            self.user_id
+        ));
+        json.push_str(&format!(r#"{{"trait_type":"rank","value":{}}},"#, pin_id));
+        json.push_str(&format!(
+            r#"{{"trait_type":"mintDate","value":"{}","display_type":"date"}},"#,
+            self.mint_date
+        ));
+        json.push_str(&format!(
+            r#"{{"trait_type":"actionDate","value":"{}","display_type":"date"}},"#,
+            self.created_at
+        ));
+        json.push_str(&format!(
+            r#"{{"trait_type":"status","value":"{}"}}"#,
+            if self.revoked { "revoked" } else { "active" }
+        ));
+        json.push(']');
+        json.push('}');
+        json
+    }
+
+    /// Mirrors the contract's `_encoded_metadata`, i.e. returns the base64 encoded json as a data
+    /// uri.
+    pub fn encoded_metadata(&self, pin_id: u64) -> String {
+        format!(
+            "{}{}",
+            DATA_URI_PREFIX,
+            STANDARD.encode(self.encode(pin_id))
+        )
+    }
+}
+
+fn action_str(action: &GuildAction) -> &'static str {
+    match action {
+        GuildAction::Joined => "Joined",
+        GuildAction::Owner => "Owner of",
+        GuildAction::Admin => "Admin of",
+        GuildAction::Role(_) => "Role in",
+    }
+}
+
+fn action_description(action: &GuildAction) -> &'static str {
+    match action {
+        GuildAction::Joined => " joined ",
+        GuildAction::Owner => "'re the owner of ",
+        GuildAction::Admin => "'re the admin of ",
+        GuildAction::Role(_) => " have a role in ",
+    }
+}
+
+// NOTE mimicking src/common/utils.sw, which only removes trailing spaces
+fn unpad(s: &str) -> &str {
+    s.trim_end_matches(' ')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::CID64;
+
+    #[test]
+    fn encode_pin_data() {
+        let pin_data = PinData {
+            holder: Address::zeroed(),
+            action: GuildAction::Owner,
+            user_id: 100,
+            guild_id: 1234,
+            guild_name: SizedAsciiString::new_with_right_whitespace_padding(
+                "MyAwesomeGuild".to_string(),
+            )
+            .unwrap(),
+            created_at: 100_000,
+            mint_date: 1_700_000_000,
+            cid: SizedAsciiString::new_with_right_whitespace_padding(CID64.to_string()).unwrap(),
+            revoked: false,
+        };
+
+        let json = pin_data.encode(7);
+        let expected = [
+            r#"{"name":"Owner of","#,
+            r#""description":"This is an onchain proof that you're the owner of MyAwesomeGuild on Guild.xyz","#,
+            r#""image":"ipfs://"#,
+            CID64,
+            r#"","attributes":["#,
+            r#"{"trait_type":"type","value":"Owner of"},"#,
+            r#"{"trait_type":"guildId","value":1234},"#,
+            r#"{"trait_type":"userId","value":100},"#,
+            r#"{"trait_type":"rank","value":7},"#,
+            r#"{"trait_type":"mintDate","value":"1700000000","display_type":"date"},"#,
+            r#"{"trait_type":"actionDate","value":"100000","display_type":"date"},"#,
+            r#"{"trait_type":"status","value":"active"}]}"#,
+        ]
+        .concat();
+        assert_eq!(json, expected);
+
+        let token_uri: TokenUri = serde_json::from_str(&json).unwrap();
+        assert_eq!(token_uri.name, Action::Owner);
+        assert_eq!(token_uri.attributes[3], Attribute::Rank(7));
+        assert_eq!(token_uri.attributes[4].mint_date(), Some(1_700_000_000));
+        assert_eq!(token_uri.attributes[6], Attribute::Status(Status::Active));
+
+        let encoded = pin_data.encoded_metadata(7);
+        let decoded = STANDARD
+            .decode(encoded.strip_prefix(DATA_URI_PREFIX).unwrap())
+            .unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), json);
+    }
+
+    #[test]
+    fn encode_role_pin_data() {
+        let pin_data = PinData {
+            holder: Address::zeroed(),
+            action: GuildAction::Role(42),
+            user_id: 100,
+            guild_id: 1234,
+            guild_name: SizedAsciiString::new_with_right_whitespace_padding(
+                "MyAwesomeGuild".to_string(),
+            )
+            .unwrap(),
+            created_at: 100_000,
+            mint_date: 1_700_000_000,
+            cid: SizedAsciiString::new_with_right_whitespace_padding(CID64.to_string()).unwrap(),
+            revoked: false,
+        };
+
+        let json = pin_data.encode(7);
+        assert!(json.starts_with(
+            r#"{"name":"Role in","description":"This is an onchain proof that you have a role in MyAwesomeGuild on Guild.xyz","#
+        ));
+        assert!(json.contains(
+            r#"{"trait_type":"guildId","value":1234},{"trait_type":"roleId","value":42},{"trait_type":"userId","value":100},"#
+        ));
+
+        let token_uri: TokenUri = serde_json::from_str(&json).unwrap();
+        assert_eq!(token_uri.name, Action::Role);
+        assert_eq!(token_uri.attributes.len(), 8);
+        assert_eq!(token_uri.attributes[2], Attribute::RoleId(42));
+    }
+}
@@ -1,24 +1,33 @@
-use crate::contract::ClaimParameters;
-use crate::utils::{bytes_to_b256, hash_params};
+use crate::contract::{ClaimParameters, UpdateParameters};
+use crate::error::ParametersError;
+use crate::keystore::{decrypt_forc_wallet, decrypt_secret_key, decrypt_seed, Password};
+use crate::utils::{bytes_to_b256, hash_params, hash_update_params};
 use fuels::accounts::provider::Provider;
+use fuels::accounts::wallet::Wallet;
 use fuels::crypto::SecretKey;
-use fuels::prelude::{launch_custom_provider_and_get_wallets, WalletUnlocked, WalletsConfig};
-use fuels::types::{Bits256, EvmAddress, Identity, Salt, B512};
+use fuels::prelude::{
+    launch_custom_provider_and_get_wallets, AssetConfig, WalletUnlocked, WalletsConfig,
+};
+use fuels::types::{Address, AssetId, Bits256, EvmAddress, Identity, Salt, B512};
 use signrs::eth::EthSigner;
 use signrs::Signer;
 
 use std::path::Path;
 use std::str::FromStr;
 
+/// Non-base asset every test wallet is funded with, besides the base asset.
+pub const TEST_ASSET_ID: AssetId = AssetId::new([1u8; 32]);
+
 pub struct ParametersBuilder {
     pub fee: u64,
     pub genesis_balance: u64,
     pub signer_seed: [u8; 32],
     pub signer_alt_seed: [u8; 32],
     pub owner_sk: Option<SecretKey>,
-    pub treasury_sk: Option<SecretKey>,
+    pub treasury: Option<Address>,
     pub url: String,
     pub salt: Salt,
+    pub soulbound: bool,
 }
 
 impl Default for ParametersBuilder {
@@ -29,9 +38,10 @@ impl Default for ParametersBuilder {
             signer_seed: [11u8; 32],
             signer_alt_seed: [22u8; 32],
             owner_sk: None,
-            treasury_sk: None,
+            treasury: None,
             url: String::new(),
             salt: Salt::default(),
+            soulbound: false,
         }
     }
 }
@@ -51,23 +61,71 @@ impl ParametersBuilder {
         self
     }
 
-    pub fn signer_file(mut self, path: impl AsRef<Path>) -> Self {
-        let signer_seed_string = std::fs::read_to_string(path).unwrap();
-        self.signer_seed = serde_json::from_str(&signer_seed_string).unwrap();
-        self
+    pub fn signer_file(mut self, path: impl AsRef<Path>) -> Result<Self, ParametersError> {
+        let signer_seed_string = std::fs::read_to_string(path)?;
+        self.signer_seed = serde_json::from_str(&signer_seed_string)?;
+        Ok(self)
     }
 
-    pub fn owner_file(mut self, path: impl AsRef<Path>) -> Self {
-        let secret_key_string = std::fs::read_to_string(path).unwrap();
-        let secret_key = SecretKey::from_str(secret_key_string.trim_end_matches('\n')).unwrap();
-        self.owner_sk = Some(secret_key);
-        self
+    pub fn owner_file(mut self, path: impl AsRef<Path>) -> Result<Self, ParametersError> {
+        self.owner_sk = Some(read_secret_key(path)?);
+        Ok(self)
+    }
+
+    /// Sets the treasury address from a secret key file.
+    ///
+    /// The treasury never signs anything, so only its address is kept.
+    pub fn treasury_file(mut self, path: impl AsRef<Path>) -> Result<Self, ParametersError> {
+        let wallet = WalletUnlocked::new_from_private_key(read_secret_key(path)?, None);
+        self.treasury = Some(wallet.address().into());
+        Ok(self)
+    }
+
+    /// Loads the signer seed from an encrypted JSON keystore.
+    pub fn signer_keystore(
+        mut self,
+        path: impl AsRef<Path>,
+        password: &Password,
+    ) -> Result<Self, ParametersError> {
+        self.signer_seed = decrypt_seed(path, password, "signer")?;
+        Ok(self)
     }
 
-    pub fn treasury_file(mut self, path: impl AsRef<Path>) -> Self {
-        let secret_key_string = std::fs::read_to_string(path).unwrap();
-        let secret_key = SecretKey::from_str(secret_key_string.trim_end_matches('\n')).unwrap();
-        self.treasury_sk = Some(secret_key);
+    /// Loads the owner's secret key from an encrypted JSON keystore.
+    pub fn owner_keystore(
+        mut self,
+        path: impl AsRef<Path>,
+        password: &Password,
+    ) -> Result<Self, ParametersError> {
+        self.owner_sk = Some(decrypt_secret_key(path, password, "owner")?);
+        Ok(self)
+    }
+
+    /// Loads the owner's secret key from the given account of a forc-wallet vault.
+    pub fn owner_forc_wallet(
+        mut self,
+        path: impl AsRef<Path>,
+        account_index: usize,
+        password: &Password,
+    ) -> Result<Self, ParametersError> {
+        self.owner_sk = Some(decrypt_forc_wallet(path, account_index, password, "owner")?);
+        Ok(self)
+    }
+
+    /// Sets the treasury address from an encrypted JSON keystore.
+    pub fn treasury_keystore(
+        mut self,
+        path: impl AsRef<Path>,
+        password: &Password,
+    ) -> Result<Self, ParametersError> {
+        let secret_key = decrypt_secret_key(path, password, "treasury")?;
+        let wallet = WalletUnlocked::new_from_private_key(secret_key, None);
+        self.treasury = Some(wallet.address().into());
+        Ok(self)
+    }
+
+    pub fn treasury(mut self, treasury: Address) -> Self {
+        self.treasury = Some(treasury);
         self
     }
 
@@ -81,36 +139,45 @@ impl ParametersBuilder {
         self
     }
 
-    pub async fn build(self) -> Parameters {
-        let provider = Provider::connect(&self.url).await.unwrap();
-        Parameters {
+    /// Keeps minted pins in the contract's custody, see [`crate::contract::GuildPinContract`].
+    pub fn soulbound(mut self, soulbound: bool) -> Self {
+        self.soulbound = soulbound;
+        self
+    }
+
+    pub async fn build(self) -> Result<Parameters, ParametersError> {
+        let owner_sk = self.owner_sk.ok_or(ParametersError::MissingRole("owner"))?;
+        let treasury = self
+            .treasury
+            .ok_or(ParametersError::MissingRole("treasury"))?;
+        let provider = Provider::connect(&self.url).await?;
+        Ok(Parameters {
             contract: WalletUnlocked::new_random(Some(provider.clone())),
-            owner: WalletUnlocked::new_from_private_key(
-                self.owner_sk.unwrap(),
-                Some(provider.clone()),
-            ),
-            treasury: WalletUnlocked::new_from_private_key(
-                self.treasury_sk.unwrap(),
-                Some(provider.clone()),
-            ),
+            owner: WalletUnlocked::new_from_private_key(owner_sk, Some(provider.clone())),
+            treasury: Wallet::from_address(treasury.into(), Some(provider.clone())),
             signer: EthSigner::new(&self.signer_seed),
             signer_alt: EthSigner::new(&self.signer_alt_seed),
             fee: self.fee,
             salt: self.salt,
+            soulbound: self.soulbound,
             alice: WalletUnlocked::new_random(Some(provider.clone())),
             bob: WalletUnlocked::new_random(Some(provider.clone())),
             charlie: WalletUnlocked::new_random(Some(provider)),
-        }
+        })
     }
 
     pub async fn test(self) -> Parameters {
         let number_of_wallets = 6;
         let coins_per_wallet = 1;
-        let wallet_config = WalletsConfig::new(
-            Some(number_of_wallets),
-            Some(coins_per_wallet),
-            Some(self.genesis_balance),
-        );
+        let assets = [AssetId::zeroed(), TEST_ASSET_ID]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: coins_per_wallet,
+                coin_amount: self.genesis_balance,
+            })
+            .collect();
+        let wallet_config = WalletsConfig::new_multiple_assets(number_of_wallets, assets);
         let mut wallets = launch_custom_provider_and_get_wallets(wallet_config, None, None)
             .await
             .unwrap();
@@ -118,11 +185,12 @@ impl ParametersBuilder {
         Parameters {
             contract: wallets.pop().unwrap(),
             owner: wallets.pop().unwrap(),
-            treasury: wallets.pop().unwrap(),
+            treasury: wallets.pop().unwrap().lock(),
             signer: EthSigner::new(&self.signer_seed),
             signer_alt: EthSigner::new(&self.signer_alt_seed),
             fee: self.fee,
             salt: self.salt,
+            soulbound: self.soulbound,
             alice: wallets.pop().unwrap(),
             bob: wallets.pop().unwrap(),
             charlie: wallets.pop().unwrap(),
@@ -133,11 +201,13 @@ impl ParametersBuilder {
 pub struct Parameters {
     pub contract: WalletUnlocked,
     pub owner: WalletUnlocked,
-    pub treasury: WalletUnlocked,
+    /// Only the treasury's address is needed, it never signs transactions
+    pub treasury: Wallet,
     pub signer: EthSigner,
     pub signer_alt: EthSigner,
     pub fee: u64,
     pub salt: Salt,
+    pub soulbound: bool,
     pub alice: WalletUnlocked,
     pub bob: WalletUnlocked,
     pub charlie: WalletUnlocked,
@@ -204,14 +274,35 @@ impl Parameters {
     pub fn sign_alt_claim(&self, params: &ClaimParameters) -> B512 {
         _sign_claim(params, &self.signer_alt)
     }
+
+    pub fn sign_update(&self, params: &UpdateParameters) -> B512 {
+        _sign_update(params, &self.signer)
+    }
+
+    pub fn sign_alt_update(&self, params: &UpdateParameters) -> B512 {
+        _sign_update(params, &self.signer_alt)
+    }
+}
+
+fn read_secret_key(path: impl AsRef<Path>) -> Result<SecretKey, ParametersError> {
+    let secret_key_string = std::fs::read_to_string(path)?;
+    SecretKey::from_str(secret_key_string.trim_end_matches('\n'))
+        .map_err(|e| ParametersError::InvalidSecretKey(e.to_string()))
 }
 
 // NOTE fuel uses the compact signature representation: https://eips.ethereum.org/EIPS/eip-2098
 // I'm deliberately not using the sdk's fuel_crypto types (SecretKey, Signature, etc) because
 // I want to mimic the backend signer, who uses an Ethers wallet to sign messages
-fn _sign_claim(params: &ClaimParameters, signer: &EthSigner) -> B512 {
-    let hashed_params = hash_params(params);
-    let signature = signer.sign(&hashed_params);
+pub(crate) fn _sign_claim(params: &ClaimParameters, signer: &EthSigner) -> B512 {
+    sign_hashed(&hash_params(params), signer)
+}
+
+pub(crate) fn _sign_update(params: &UpdateParameters, signer: &EthSigner) -> B512 {
+    sign_hashed(&hash_update_params(params), signer)
+}
+
+fn sign_hashed(hashed_params: &[u8; 32], signer: &EthSigner) -> B512 {
+    let signature = signer.sign(hashed_params);
     let parity = signature[64] - 27;
     debug_assert!(parity < 2);
     let mut hi = Bits256::zeroed();
@@ -0,0 +1,249 @@
+use crate::contract::GuildPinContract;
+use crate::error::Result;
+use fuels::accounts::provider::Provider;
+use fuels::types::bech32::Bech32Address;
+use fuels::types::{Address, AssetId};
+use serde::Serialize;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Number of pin owners fetched per call when walking the minted pins.
+const PAGE_SIZE: u64 = 100;
+
+/// Recorded vs. actual pin holdings of a single address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HolderDrift {
+    pub holder: Address,
+    /// The holder's `balance_of` in the contract.
+    pub recorded_balance: u64,
+    /// Pins recorded for the holder in `pin_owners`.
+    pub recorded_pins: Vec<u64>,
+    /// Recorded pins whose asset the holder doesn't actually hold.
+    pub missing_pins: Vec<u64>,
+    /// Pins whose asset the holder holds while they are recorded for another owner.
+    pub unrecorded_pins: Vec<u64>,
+}
+
+impl HolderDrift {
+    /// Number of pins the holder actually holds.
+    pub fn held_balance(&self) -> u64 {
+        (self.recorded_pins.len() - self.missing_pins.len() + self.unrecorded_pins.len()) as u64
+    }
+
+    /// Returns `true` if the contract's maps disagree with the holder's native balances.
+    pub fn has_drift(&self) -> bool {
+        !self.missing_pins.is_empty()
+            || !self.unrecorded_pins.is_empty()
+            || self.recorded_balance != self.recorded_pins.len() as u64
+    }
+}
+
+/// Result of comparing the contract's ownership maps with the native pin asset balances.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DriftReport {
+    /// Every holder found in `pin_owners` and every checked address holding someone else's pin,
+    /// ordered by address.
+    pub holders: Vec<HolderDrift>,
+}
+
+impl DriftReport {
+    /// Holders whose recorded and actual holdings differ.
+    pub fn drifted(&self) -> impl Iterator<Item = &HolderDrift> {
+        self.holders.iter().filter(|holder| holder.has_drift())
+    }
+
+    pub fn has_drift(&self) -> bool {
+        self.drifted().next().is_some()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Writes one line per holder, pin ids are separated by semicolons.
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            "holder,recorded_balance,held_balance,drift,missing_pins,unrecorded_pins\n".to_string();
+        for holder in &self.holders {
+            // writing into a string never fails
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                holder.holder,
+                holder.recorded_balance,
+                holder.held_balance(),
+                holder.has_drift(),
+                join_pins(&holder.missing_pins),
+                join_pins(&holder.unrecorded_pins),
+            );
+        }
+        csv
+    }
+}
+
+fn join_pins(pins: &[u64]) -> String {
+    pins.iter()
+        .map(u64::to_string)
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+/// Checks whether the recorded owner of every pin actually holds the pin's asset.
+///
+/// Pins transferred away from their recorded owner are reported as missing, which means that
+/// `pin_id_by_address` and `balance_of` are no longer reliable for that owner. The chain can't be
+/// queried for the holders of an asset, so pins are only reported as unrecorded for the recorded
+/// owners themselves and for the addresses in `candidates`. In soulbound mode the contract is
+/// expected to hold every recorded pin instead.
+///
+/// Owners are fetched [`PAGE_SIZE`] pins at a time and the balances of every checked address are
+/// fetched at once, so the number of calls grows with the number of holders, not with the number
+/// of pins.
+pub async fn reconcile(
+    contract: &GuildPinContract,
+    provider: &Provider,
+    candidates: &[Address],
+) -> Result<DriftReport> {
+    let total_minted = contract.total_minted().await?;
+    let mut recorded = BTreeMap::<Address, Vec<u64>>::new();
+    let mut pins_by_asset = HashMap::<AssetId, u64>::new();
+    let mut start = 0;
+    while start < total_minted {
+        let owners = contract.pin_owners(start, PAGE_SIZE).await?;
+        for (pin_id, owner) in (start..).zip(owners) {
+            if let Some(owner) = owner {
+                recorded.entry(owner).or_default().push(pin_id);
+                pins_by_asset.insert(contract.asset_id(pin_id), pin_id);
+            }
+        }
+        start += PAGE_SIZE;
+    }
+
+    let mut checked = recorded.keys().copied().collect::<BTreeSet<Address>>();
+    checked.extend(candidates);
+
+    let custody = if contract.soulbound().await? {
+        let balances = provider
+            .get_contract_balances(contract.bech_contract_id())
+            .await?;
+        Some(held_pins(&pins_by_asset, balances))
+    } else {
+        None
+    };
+
+    let mut holders = Vec::with_capacity(checked.len());
+    for holder in checked {
+        let recorded_pins = recorded.remove(&holder).unwrap_or_default();
+        let held = match &custody {
+            // nobody but the contract can hold a soulbound pin
+            Some(custody) => recorded_pins
+                .iter()
+                .copied()
+                .filter(|pin_id| custody.contains(pin_id))
+                .collect(),
+            None => {
+                let balances = provider
+                    .get_balances(&Bech32Address::from(holder))
+                    .await?
+                    .into_iter()
+                    .filter_map(|(asset_id, amount)| {
+                        Some((AssetId::from_str(&asset_id).ok()?, amount))
+                    });
+                held_pins(&pins_by_asset, balances)
+            }
+        };
+
+        let missing_pins = recorded_pins
+            .iter()
+            .copied()
+            .filter(|pin_id| !held.contains(pin_id))
+            .collect::<Vec<u64>>();
+        let unrecorded_pins = held
+            .iter()
+            .copied()
+            .filter(|pin_id| !recorded_pins.contains(pin_id))
+            .collect::<Vec<u64>>();
+        if recorded_pins.is_empty() && unrecorded_pins.is_empty() {
+            continue;
+        }
+
+        holders.push(HolderDrift {
+            holder,
+            recorded_balance: contract.balance_of(holder).await?,
+            recorded_pins,
+            missing_pins,
+            unrecorded_pins,
+        });
+    }
+
+    Ok(DriftReport { holders })
+}
+
+/// Ids of the recorded pins among the given asset balances.
+fn held_pins(
+    pins_by_asset: &HashMap<AssetId, u64>,
+    balances: impl IntoIterator<Item = (AssetId, u64)>,
+) -> BTreeSet<u64> {
+    balances
+        .into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .filter_map(|(asset_id, _)| pins_by_asset.get(&asset_id).copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn drift_report_csv() {
+        let report = DriftReport {
+            holders: vec![
+                HolderDrift {
+                    holder: Address::from([1u8; 32]),
+                    recorded_balance: 2,
+                    recorded_pins: vec![0, 2],
+                    missing_pins: vec![],
+                    unrecorded_pins: vec![],
+                },
+                HolderDrift {
+                    holder: Address::from([2u8; 32]),
+                    recorded_balance: 2,
+                    recorded_pins: vec![1, 3],
+                    missing_pins: vec![1, 3],
+                    unrecorded_pins: vec![],
+                },
+                HolderDrift {
+                    holder: Address::from([3u8; 32]),
+                    recorded_balance: 0,
+                    recorded_pins: vec![],
+                    missing_pins: vec![],
+                    unrecorded_pins: vec![1],
+                },
+            ],
+        };
+
+        assert!(report.has_drift());
+        assert_eq!(report.drifted().count(), 2);
+        let lines = report
+            .to_csv()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            format!("{},2,2,false,,", Address::from([1u8; 32]))
+        );
+        assert_eq!(
+            lines[2],
+            format!("{},2,0,true,1;3,", Address::from([2u8; 32]))
+        );
+        assert_eq!(
+            lines[3],
+            format!("{},0,1,true,,1", Address::from([3u8; 32]))
+        );
+    }
+}
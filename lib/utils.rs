@@ -1,4 +1,6 @@
-use crate::contract::{ClaimParameters, GuildAction};
+use crate::contract::{ClaimParameters, GuildAction, UpdateParameters};
+use fuels::accounts::provider::Provider;
+use fuels::crypto::Hasher;
 use fuels::types::{Address, Bits256, ContractId, Identity, SizedAsciiString};
 use sha3::digest::Digest;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -13,10 +15,36 @@ pub fn keccak256<T: AsRef<[u8]>>(input: T) -> [u8; 32] {
     output
 }
 
+/// Mimics `pin_sub_id` in the contract, i.e. the sha256 hash of the big-endian pin id.
+pub fn pin_sub_id(pin_id: u64) -> Bits256 {
+    Bits256(*Hasher::hash(pin_id.to_be_bytes()))
+}
+
 pub fn hash_params(params: &ClaimParameters) -> [u8; 32] {
     keccak256(params_to_bytes(params))
 }
 
+/// Mimics `ClaimParameters::to_message` in the contract, i.e. prefixes the hashed parameters with
+/// the Ethereum signed message header and hashes them again.
+pub fn claim_message(params: &ClaimParameters) -> [u8; 32] {
+    eth_message(&hash_params(params))
+}
+
+pub fn hash_update_params(params: &UpdateParameters) -> [u8; 32] {
+    keccak256(update_params_to_bytes(params))
+}
+
+/// Mimics `UpdateParameters::to_message` in the contract.
+pub fn update_message(params: &UpdateParameters) -> [u8; 32] {
+    eth_message(&hash_update_params(params))
+}
+
+fn eth_message(hashed_message: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = b"\x19Ethereum Signed Message:\n32".to_vec();
+    bytes.extend_from_slice(hashed_message);
+    keccak256(bytes)
+}
+
 // NOTE mimicking sway-lib-std/src/identity.sw hash impl
 fn hash_identity(identity: &Identity, bytes: &mut Vec<u8>) {
     match identity {
@@ -35,6 +63,10 @@ pub fn params_to_bytes(params: &ClaimParameters) -> Vec<u8> {
     let mut bytes = Vec::new();
     bytes.extend_from_slice(params.recipient.as_slice());
     bytes.push(action_byte(&params.action));
+    // NOTE mimicking the contract's `GuildAction` hash impl, the role id follows the action byte
+    if let GuildAction::Role(role_id) = params.action {
+        bytes.extend_from_slice(&role_id.to_be_bytes());
+    }
     bytes.extend_from_slice(&params.user_id.to_be_bytes());
     bytes.extend_from_slice(&params.guild_id.to_be_bytes());
     bytes.extend_from_slice(params.guild_name.as_ref());
@@ -48,11 +80,23 @@ pub fn params_to_bytes(params: &ClaimParameters) -> Vec<u8> {
     bytes
 }
 
+pub fn update_params_to_bytes(params: &UpdateParameters) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&params.pin_id.to_be_bytes());
+    bytes.extend_from_slice(params.guild_name.as_ref());
+    bytes.extend_from_slice(params.cid.as_ref());
+    bytes.extend_from_slice(&params.signed_at.to_be_bytes());
+    bytes.extend_from_slice(&params.chain_id.to_be_bytes());
+    bytes.extend_from_slice(params.contract_id.as_ref());
+    bytes
+}
+
 fn action_byte(action: &GuildAction) -> u8 {
     match action {
         GuildAction::Joined => 0,
         GuildAction::Owner => 1,
         GuildAction::Admin => 2,
+        GuildAction::Role(_) => 3,
     }
 }
 
@@ -86,7 +130,10 @@ pub struct ClaimBuilder {
 }
 
 impl ClaimBuilder {
-    pub fn new(recipient: Address, contract_id: ContractId) -> Self {
+    /// Creates a builder with default values, signed for the given chain.
+    ///
+    /// Use [`ClaimBuilder::for_provider`] to target the network of a provider.
+    pub fn new(recipient: Address, contract_id: ContractId, chain_id: u64) -> Self {
         Self {
             recipient,
             action: GuildAction::Joined,
@@ -102,13 +149,18 @@ impl ClaimBuilder {
                 .unwrap()
                 .as_secs(),
             cid: SizedAsciiString::new_with_right_whitespace_padding(CID64.to_string()).unwrap(),
-            chain_id: 9999,
+            chain_id,
             admin_treasury: Identity::ContractId(contract_id),
             admin_fee: 0,
             contract_id,
         }
     }
 
+    /// Creates a builder with default values and the consensus chain id of the provider.
+    pub fn for_provider(recipient: Address, contract_id: ContractId, provider: &Provider) -> Self {
+        Self::new(recipient, contract_id, *provider.chain_id())
+    }
+
     pub fn action(mut self, action: GuildAction) -> Self {
         self.action = action;
         self
@@ -162,6 +214,74 @@ impl ClaimBuilder {
     }
 }
 
+/// Builds the parameters of a backend-signed pin metadata update.
+///
+/// The guild name and the cid default to the values [`ClaimBuilder`] uses.
+pub struct UpdateBuilder {
+    pub pin_id: u64,
+    pub guild_name: SizedAsciiString<64>,
+    pub cid: SizedAsciiString<64>,
+    pub signed_at: u64,
+    pub chain_id: u64,
+    pub contract_id: ContractId,
+}
+
+impl UpdateBuilder {
+    /// Creates a builder with default values, signed for the given chain.
+    pub fn new(pin_id: u64, contract_id: ContractId, chain_id: u64) -> Self {
+        let claim = ClaimBuilder::new(Address::zeroed(), contract_id, chain_id);
+        Self {
+            pin_id,
+            guild_name: claim.guild_name,
+            cid: claim.cid,
+            signed_at: claim.signed_at,
+            chain_id: claim.chain_id,
+            contract_id,
+        }
+    }
+
+    /// Creates a builder with default values and the consensus chain id of the provider.
+    pub fn for_provider(pin_id: u64, contract_id: ContractId, provider: &Provider) -> Self {
+        Self::new(pin_id, contract_id, *provider.chain_id())
+    }
+
+    /// Sets the new guild name, panics if it's longer than 64 characters or not ascii.
+    pub fn guild_name(mut self, guild_name: &str) -> Self {
+        self.guild_name =
+            SizedAsciiString::new_with_right_whitespace_padding(guild_name.to_string())
+                .expect("invalid guild name");
+        self
+    }
+
+    /// Sets the new cid, panics if it's longer than 64 characters or not ascii.
+    pub fn cid(mut self, cid: &str) -> Self {
+        self.cid = SizedAsciiString::new_with_right_whitespace_padding(cid.to_string())
+            .expect("invalid cid");
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    pub fn signed_at(mut self, signed_at: u64) -> Self {
+        self.signed_at = signed_at;
+        self
+    }
+
+    pub fn build(self) -> UpdateParameters {
+        UpdateParameters {
+            pin_id: self.pin_id,
+            guild_name: self.guild_name,
+            cid: self.cid,
+            signed_at: self.signed_at,
+            chain_id: self.chain_id,
+            contract_id: self.contract_id,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -181,6 +301,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn update_params_layout() {
+        let params = UpdateBuilder::new(7, ContractId::zeroed(), 0)
+            .guild_name("Renamed")
+            .build();
+        let bytes = update_params_to_bytes(&params);
+        // pin id, guild name, cid, signed at, chain id and contract id
+        assert_eq!(bytes.len(), 8 + 64 + 64 + 8 + 8 + 32);
+        assert_eq!(bytes[..8], 7u64.to_be_bytes());
+        assert_eq!(&bytes[8..15], b"Renamed");
+        assert_eq!(bytes[15], b' ');
+        assert_ne!(update_message(&params), hash_update_params(&params));
+    }
+
     #[test]
     fn tai64() {
         let unix = 1234567890;
@@ -0,0 +1,194 @@
+use crate::contract::ClaimParameters;
+use crate::error::GuildPinError;
+use crate::utils::{bytes_to_b256, claim_message, keccak256};
+use fuels::crypto::{Message, Signature};
+use fuels::types::{ContractId, EvmAddress, B512};
+
+/// Default value of the contract's `SIGNATURE_VALIDITY_PERIOD` configurable (in seconds).
+pub const SIGNATURE_VALIDITY_PERIOD: u64 = 3600;
+
+/// Outcome of checking a signed claim offline, the same way `_check_signature` does on-chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureReport {
+    /// Address recovered from the signature, `None` if recovery failed.
+    pub recovered: Option<EvmAddress>,
+    /// The recovered address is the expected signer.
+    pub signer_matches: bool,
+    /// The signature is older than the validity period.
+    pub expired: bool,
+    /// The signed contract id is the expected contract id.
+    pub contract_id_matches: bool,
+    /// The signed chain id is the expected chain id.
+    pub chain_id_matches: bool,
+}
+
+impl SignatureReport {
+    /// Returns `true` if the contract would accept the signature.
+    pub fn is_valid(&self) -> bool {
+        self.signer_matches && !self.expired && self.contract_id_matches && self.chain_id_matches
+    }
+}
+
+/// Outcome of checking everything `_claim` would check before actually submitting a claim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimPreflight {
+    /// The contract has been initialized.
+    pub initialized: bool,
+    /// Signature checks against the contract's signer and the latest block time.
+    pub signature: SignatureReport,
+    /// The signed claim has already been used to mint a pin.
+    pub consumed: bool,
+    /// Pin already claimed by the recipient for the same guild and action.
+    pub claimed_by_address: Option<u64>,
+    /// Pin already claimed by the user for the same guild and action.
+    pub claimed_by_user_id: Option<u64>,
+    /// Amount of base asset the claim requires (fee + admin fee), `None` if the contract is not
+    /// initialized or doesn't accept the base asset.
+    pub required_amount: Option<u64>,
+    /// The caller's base asset balance (gas costs are not accounted for).
+    pub balance: u64,
+}
+
+impl ClaimPreflight {
+    /// Returns `true` if the claim is expected to succeed.
+    pub fn is_ok(&self) -> bool {
+        self.errors().is_empty()
+    }
+
+    /// Returns the errors the claim would fail with, in the order the contract checks them.
+    pub fn errors(&self) -> Vec<GuildPinError> {
+        let mut errors = Vec::new();
+        if !self.initialized {
+            errors.push(GuildPinError::NotInitialized);
+        }
+        if !self.signature.contract_id_matches {
+            errors.push(GuildPinError::InvalidContractId);
+        }
+        if !self.signature.chain_id_matches {
+            errors.push(GuildPinError::InvalidChainId);
+        }
+        if self.signature.expired {
+            errors.push(GuildPinError::ExpiredSignature);
+        }
+        if !self.signature.signer_matches {
+            errors.push(GuildPinError::InvalidSignature);
+        }
+        if self.consumed {
+            errors.push(GuildPinError::MessageAlreadyConsumed);
+        }
+        if self.claimed_by_address.is_some() || self.claimed_by_user_id.is_some() {
+            errors.push(GuildPinError::AlreadyClaimed);
+        }
+        match self.required_amount {
+            Some(required_amount) if self.balance < required_amount => {
+                errors.push(GuildPinError::InsufficientAmount)
+            }
+            // an uninitialized contract accepts no assets at all, that's already reported
+            None if self.initialized => errors.push(GuildPinError::AssetNotAccepted),
+            _ => {}
+        }
+        errors
+    }
+}
+
+/// Recovers the EVM address of the account that signed the claim.
+///
+/// The signature is expected in the EIP-2098 compact form produced by the backend signer.
+pub fn recover_signer(params: &ClaimParameters, signature: B512) -> Option<EvmAddress> {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&signature.bytes[0].0);
+    bytes[32..].copy_from_slice(&signature.bytes[1].0);
+    let signature = Signature::from_bytes(bytes);
+    let message = Message::from_bytes(claim_message(params));
+    let public_key = signature.recover(&message).ok()?;
+    let hashed_public_key = keccak256(public_key.as_ref());
+    Some(EvmAddress::from(bytes_to_b256(&hashed_public_key[12..])))
+}
+
+/// Checks a signed claim against the expected signer and contract without submitting anything.
+///
+/// `timestamp` is the unix timestamp the expiry is checked against, e.g. the latest block time.
+pub fn verify_claim_signature(
+    params: &ClaimParameters,
+    signature: B512,
+    signer: EvmAddress,
+    contract_id: ContractId,
+    chain_id: u64,
+    timestamp: u64,
+    validity_period: u64,
+) -> SignatureReport {
+    let recovered = recover_signer(params, signature);
+    SignatureReport {
+        recovered,
+        signer_matches: recovered == Some(signer),
+        expired: params.signed_at <= timestamp.saturating_sub(validity_period),
+        contract_id_matches: params.contract_id == contract_id,
+        chain_id_matches: params.chain_id == chain_id,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parameters::_sign_claim;
+    use crate::utils::ClaimBuilder;
+    use fuels::types::Address;
+    use signrs::eth::EthSigner;
+    use signrs::Signer;
+
+    #[test]
+    fn verify_signature() {
+        let signer = EthSigner::new(&[11u8; 32]);
+        let signer_alt = EthSigner::new(&[22u8; 32]);
+        let signer_evm = EvmAddress::from(bytes_to_b256(&signer.address()));
+        let contract_id = ContractId::from([1u8; 32]);
+        let params = ClaimBuilder::new(Address::from([2u8; 32]), contract_id, 0)
+            .signed_at(10_000)
+            .build();
+        let now = 10_100;
+
+        let signature = _sign_claim(&params, &signer);
+        let report = verify_claim_signature(
+            &params,
+            signature,
+            signer_evm,
+            contract_id,
+            0,
+            now,
+            SIGNATURE_VALIDITY_PERIOD,
+        );
+        assert_eq!(report.recovered, Some(signer_evm));
+        assert!(report.is_valid());
+
+        // signed by someone else
+        let signature = _sign_claim(&params, &signer_alt);
+        let report = verify_claim_signature(
+            &params,
+            signature,
+            signer_evm,
+            contract_id,
+            0,
+            now,
+            SIGNATURE_VALIDITY_PERIOD,
+        );
+        assert!(!report.signer_matches);
+        assert!(!report.is_valid());
+
+        // expired, invalid contract id and chain id
+        let signature = _sign_claim(&params, &signer);
+        let report = verify_claim_signature(
+            &params,
+            signature,
+            signer_evm,
+            ContractId::zeroed(),
+            9999,
+            now + SIGNATURE_VALIDITY_PERIOD,
+            SIGNATURE_VALIDITY_PERIOD,
+        );
+        assert!(report.signer_matches);
+        assert!(report.expired);
+        assert!(!report.contract_id_matches);
+        assert!(!report.chain_id_matches);
+        assert!(!report.is_valid());
+    }
+}
@@ -5,10 +5,12 @@ pub mod action;
 pub mod base64;
 pub mod claim;
 pub mod pin;
+pub mod update;
 
 use ::common::action::GuildAction;
 
 use std::call_frames::get_contract_id_from_call_frame;
+use std::hash::sha256;
 use std::registers::frame_ptr;
 
 pub type BalancesMap = StorageMap<Address, u64>;
@@ -18,8 +20,24 @@ pub type TokenIdByAddressMap = StorageMap<Address, GuildIdActionTokenIdMap>;
 pub type TokenIdByUserIdMap = StorageMap<u64, StorageKey<GuildIdActionTokenIdMap>>;
 pub type TotalMintedPerGuildMap = StorageMap<u64, u64>;
 pub type TokenOfOwnerByIndexMap = StorageMap<Address, StorageMap<u64, u64>>;
+pub type AdminFeesPerGuildMap = StorageMap<u64, StorageMap<AssetId, u64>>;
+pub type ConsumedMessagesMap = StorageMap<b256, bool>;
+pub type PinIdByAssetMap = StorageMap<AssetId, u64>;
+pub type LastUpdateMap = StorageMap<u64, u64>;
+pub type FeesPerGuildActionMap = StorageMap<u64, StorageMap<u8, u64>>;
+pub type FeesPerGuildMap = StorageMap<u64, u64>;
+pub type FeesPerActionMap = StorageMap<u8, u64>;
 
 pub fn contract_id() -> ContractId {
     let current_call_frame = frame_ptr();
     get_contract_id_from_call_frame(current_call_frame)
 }
+
+/// Every pin is minted under its own sub id, i.e. every pin is a distinct asset
+pub fn pin_sub_id(pin_id: u64) -> SubId {
+    sha256(pin_id)
+}
+
+pub fn pin_asset_id(pin_id: u64) -> AssetId {
+    AssetId::new(contract_id(), pin_sub_id(pin_id))
+}
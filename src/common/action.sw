@@ -7,6 +7,8 @@ pub enum GuildAction {
     Joined: (),
     Owner: (),
     Admin: (),
+    /// Holding the role with the given id
+    Role: u64,
 }
 
 impl GuildAction {
@@ -15,6 +17,14 @@ impl GuildAction {
             GuildAction::Joined => 0,
             GuildAction::Owner => 1,
             GuildAction::Admin => 2,
+            GuildAction::Role(_) => 3,
+        }
+    }
+
+    pub fn role_id(self) -> Option<u64> {
+        match self {
+            GuildAction::Role(role_id) => Some(role_id),
+            _ => None,
         }
     }
 
@@ -23,6 +33,7 @@ impl GuildAction {
             GuildAction::Joined => "Joined",
             GuildAction::Owner => "Owner of",
             GuildAction::Admin => "Admin of",
+            GuildAction::Role(_) => "Role in",
         }
     }
 
@@ -31,14 +42,19 @@ impl GuildAction {
             GuildAction::Joined => " joined ",
             GuildAction::Owner => "'re the owner of ",
             GuildAction::Admin => "'re the admin of ",
+            GuildAction::Role(_) => " have a role in ",
         }
     }
 }
 
+// NOTE the role id is part of the hash, so pins of different roles are indexed separately
 impl Hash for GuildAction {
     fn hash(self, ref mut state: Hasher) {
         let mut bytes = Bytes::with_capacity(1);
         bytes.push(self.into_byte());
         state.write(bytes);
+        if let Some(role_id) = self.role_id() {
+            role_id.hash(state);
+        }
     }
 }
@@ -42,17 +42,19 @@ impl ClaimParameters {
     pub fn to_message(self) -> b256 {
         let mut hasher = Hasher::new();
         self.hash(hasher);
-        let hashed_msg = hasher.keccak256();
-
-        // hash again with ETH prefix
-        let mut hasher = Hasher::new();
-        // NOTE msg len will always be 32 bytes due to keccak256-hashing stuff first. Furthermore
-        // sway compiler cant handle \x19 and \n so I need to append special characters manually
-        X19.hash(hasher); // \x19
-        "Ethereum Signed Message:".hash(hasher);
-        NEWLINE.hash(hasher); // \n
-        "32".hash(hasher); // length
-        hashed_msg.hash(hasher);
-        hasher.keccak256()
+        eth_message(hasher.keccak256())
     }
 }
+
+/// Hashes the already hashed message again with the ETH prefix, like the backend signer does.
+pub fn eth_message(hashed_msg: b256) -> b256 {
+    let mut hasher = Hasher::new();
+    // NOTE msg len will always be 32 bytes due to keccak256-hashing stuff first. Furthermore
+    // sway compiler cant handle \x19 and \n so I need to append special characters manually
+    X19.hash(hasher); // \x19
+    "Ethereum Signed Message:".hash(hasher);
+    NEWLINE.hash(hasher); // \n
+    "32".hash(hasher); // length
+    hashed_msg.hash(hasher);
+    hasher.keccak256()
+}
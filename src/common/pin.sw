@@ -23,9 +23,40 @@ pub struct PinData {
     pub created_at: u64,
     pub mint_date: u64,
     pub cid: str[64],
+    pub revoked: bool,
+}
+
+pub enum RevocationReason {
+    LeftGuild: (),
+    RoleRemoved: (),
+    Other: (),
 }
 
 impl PinData {
+    pub fn description(self) -> Bytes {
+        let mut bytes = Bytes::new();
+        push_str("This is an onchain proof that you", bytes);
+        push_str(self.action.to_description(), bytes);
+        bytes.append(unpad(from_str_array(self.guild_name)));
+        push_str(" on Guild.xyz", bytes);
+        bytes
+    }
+
+    pub fn status(self) -> str {
+        if self.revoked {
+            "revoked"
+        } else {
+            "active"
+        }
+    }
+
+    pub fn image(self) -> Bytes {
+        let mut bytes = Bytes::new();
+        push_str("ipfs://", bytes);
+        bytes.append(unpad(from_str_array(self.cid)));
+        bytes
+    }
+
     // NOTE I know this is horrible but do you have a better solution? I need to format this as
     // proper json string but there's no serde here lol. Hasher's inner bytes field is not
     // accessible anymore, because it's declared as private, so I cannot use Hasher's inner state
@@ -48,10 +79,7 @@ impl PinData {
         bytes.push(Q);
         bytes.push(COLON);
         bytes.push(Q);
-        push_str("This is an onchain proof that you", bytes);
-        push_str(self.action.to_description(), bytes);
-        bytes.append(unpad(from_str_array(self.guild_name)));
-        push_str(" on Guild.xyz", bytes);
+        bytes.append(self.description());
         bytes.push(Q);
         bytes.push(COMMA);
         // image
@@ -60,8 +88,7 @@ impl PinData {
         bytes.push(Q);
         bytes.push(COLON);
         bytes.push(Q);
-        push_str("ipfs://", bytes);
-        bytes.append(unpad(from_str_array(self.cid)));
+        bytes.append(self.image());
         bytes.push(Q);
         bytes.push(COMMA);
         // attributes
@@ -82,6 +109,14 @@ impl PinData {
         value_json_u64(self.guild_id, bytes);
         bytes.push(RCB);
         bytes.push(COMMA);
+        // role_id
+        if let Some(role_id) = self.action.role_id() {
+            bytes.push(LCB);
+            type_json("roleId", bytes);
+            value_json_u64(role_id, bytes);
+            bytes.push(RCB);
+            bytes.push(COMMA);
+        }
         // user_id
         bytes.push(LCB);
         type_json("userId", bytes);
@@ -107,6 +142,12 @@ impl PinData {
         value_json(u64_to_ascii_bytes(self.created_at), false, bytes); // encode as string
         date_json(bytes);
         bytes.push(RCB);
+        bytes.push(COMMA);
+        // status
+        bytes.push(LCB);
+        type_json("status", bytes);
+        value_json_str(self.status(), bytes);
+        bytes.push(RCB);
 
         bytes.push(RSB);
         bytes.push(RCB);
@@ -0,0 +1,34 @@
+library;
+
+use ::common::claim::eth_message;
+
+use std::hash::{Hash, Hasher};
+
+/// New guild name and artwork of a pin, signed by the backend.
+pub struct UpdateParameters {
+    pub pin_id: u64,
+    pub guild_name: str[64],
+    pub cid: str[64],
+    pub signed_at: u64,
+    pub chain_id: u64,
+    pub contract_id: ContractId,
+}
+
+impl Hash for UpdateParameters {
+    fn hash(self, ref mut state: Hasher) {
+        self.pin_id.hash(state);
+        from_str_array(self.guild_name).hash(state);
+        from_str_array(self.cid).hash(state);
+        self.signed_at.hash(state);
+        self.chain_id.hash(state);
+        self.contract_id.hash(state);
+    }
+}
+
+impl UpdateParameters {
+    pub fn to_message(self) -> b256 {
+        let mut hasher = Hasher::new();
+        self.hash(hasher);
+        eth_message(hasher.keccak256())
+    }
+}
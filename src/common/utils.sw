@@ -2,7 +2,6 @@ library;
 
 use std::bytes::Bytes;
 use std::bytes_conversions::u64::*;
-use std::primitive_conversions::u64::*;
 use std::string::String;
 use std::logging::log;
 
@@ -44,41 +43,6 @@ pub fn u64_to_ascii_bytes(num: u64) -> Bytes {
     }
 }
 
-pub fn parse_u64(s: String) -> Option<u64> {
-    let ascii_bytes = s.as_bytes();
-    let bytes_len = ascii_bytes.len();
-    if bytes_len == 0 || bytes_len > 20 {
-        // empty or too long string (u64::MAX has 20 digits) even if the input is 21 zeros, we don't
-        // allow it because it wouldn't make sense
-        return None
-    }
-
-    let mut result: u64 = 0;
-    let mut i: u64 = 0;
-
-    while i < bytes_len {
-        // NOTE unwrap is fine because we checked the length
-        let digit_u8: u8 = ascii_bytes.get(i).unwrap();
-        if digit_u8 < 48u8 || 57u8 < digit_u8 {
-            // encountered non-ascii character
-            return None
-        }
-        let digit = u64::from(digit_u8 - 48u8);
-        if u64::max() / 10 < result {
-            // multiplication overflow
-            return None
-        }
-        result *= 10;
-        if u64::max() - digit < result {
-            // addition overflow
-            return None
-        }
-        result += digit;
-        i += 1;
-    }
-    Some(result)
-}
-
 pub fn unpad(s: str) -> Bytes {
     let mut bytes = str_to_bytes(s);
     let mut len = bytes.len();
@@ -155,29 +119,3 @@ fn unpad_string() {
         String::from_ascii_str("hello"),
     );
 }
-
-#[test]
-fn parse_u64_from_string() {
-    assert(parse_u64(String::from_ascii_str("")).is_none());
-    assert(parse_u64(String::from_ascii_str("000000000000000000000")).is_none());
-    assert(parse_u64(String::from_ascii_str("111111111111111111111")).is_none());
-
-    assert_eq(parse_u64(String::from_ascii_str("0")), Some(0));
-    assert_eq(parse_u64(String::from_ascii_str("1")), Some(1));
-    assert_eq(parse_u64(String::from_ascii_str("10")), Some(10));
-    assert_eq(parse_u64(String::from_ascii_str("111")), Some(111));
-    assert_eq(
-        parse_u64(String::from_ascii_str("1234567890")),
-        Some(1234567890),
-    );
-    assert_eq(
-        parse_u64(String::from_ascii_str("2000000000000000000")),
-        Some(2000000000000000000),
-    );
-    let max_str = String::from(u64_to_ascii_bytes(u64::max()));
-    assert_eq(parse_u64(max_str), Some(u64::max()));
-
-    assert(parse_u64(String::from_ascii_str("20000000000000000000")).is_none());
-    assert(parse_u64(String::from_ascii_str("18446744073709551616")).is_none());
-    assert(parse_u64(String::from_ascii_str("18446744073709551625")).is_none());
-}
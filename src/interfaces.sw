@@ -1,7 +1,10 @@
 library;
 
 pub mod init;
+pub mod fees;
 pub mod token;
 pub mod metadata;
 pub mod owner;
+pub mod payment;
 pub mod src20;
+pub mod src3;
@@ -0,0 +1,137 @@
+library;
+
+use ::common::action::GuildAction;
+use ::interfaces::owner::_fee;
+use ::interfaces::payment::PaymentKeys;
+use sway_libs::ownership::only_owner;
+
+use std::hash::Hash;
+
+pub enum FeeError {
+    MissingFeeScope: (),
+    BaseAssetRequired: (),
+}
+
+abi FeeSchedule {
+    #[storage(read, write)]
+    fn set_fee_override(guild_id: Option<u64>, action: Option<GuildAction>, fee: Option<u64>);
+    #[storage(read)]
+    fn fee_override(guild_id: Option<u64>, action: Option<GuildAction>) -> Option<u64>;
+    #[storage(read)]
+    fn fee_for(guild_id: u64, action: GuildAction) -> u64;
+}
+
+pub struct FeeOverrideChanged {
+    pub guild_id: Option<u64>,
+    pub action: Option<GuildAction>,
+    pub old: Option<u64>,
+    pub new: Option<u64>,
+}
+
+// NOTE can't use type aliases here, see `TokenKeys`
+pub struct FeeKeys {
+    pub guild_action_fees: StorageKey<StorageMap<u64, StorageMap<u8, u64>>>,
+    pub guild_fees: StorageKey<StorageMap<u64, u64>>,
+    pub action_fees: StorageKey<StorageMap<u8, u64>>,
+}
+
+/// Sets the fee of claims in the given guild and/or of the given action, a `None` fee removes the
+/// override
+///
+/// Overrides are set per kind of action, i.e. the role id of `GuildAction::Role` is ignored and
+/// the override applies to every role. They are priced in the base asset.
+#[storage(read, write)]
+pub fn _set_fee_override(
+    guild_id: Option<u64>,
+    action: Option<GuildAction>,
+    fee: Option<u64>,
+    keys: FeeKeys,
+) {
+    only_owner();
+    let old = _fee_override(guild_id, action, keys);
+    match guild_id {
+        Some(guild_id) => match action {
+            Some(action) => {
+                _write_fee(keys.guild_action_fees.get(guild_id), action.into_byte(), fee)
+            },
+            None => _write_fee(keys.guild_fees, guild_id, fee),
+        },
+        None => match action {
+            Some(action) => _write_fee(keys.action_fees, action.into_byte(), fee),
+            None => {
+                require(false, FeeError::MissingFeeScope);
+                revert(0);
+            }
+        },
+    }
+    log(FeeOverrideChanged {
+        guild_id,
+        action,
+        old,
+        new: fee,
+    });
+}
+
+#[storage(write)]
+fn _write_fee<K>(fees: StorageKey<StorageMap<K, u64>>, key: K, fee: Option<u64>)
+where
+    K: Hash,
+{
+    match fee {
+        Some(fee) => fees.insert(key, fee),
+        None => {
+            let _ = fees.remove(key);
+        }
+    }
+}
+
+#[storage(read)]
+pub fn _fee_override(
+    guild_id: Option<u64>,
+    action: Option<GuildAction>,
+    keys: FeeKeys,
+) -> Option<u64> {
+    match guild_id {
+        Some(guild_id) => match action {
+            Some(action) => {
+                keys.guild_action_fees.get(guild_id).get(action.into_byte()).try_read()
+            },
+            None => keys.guild_fees.get(guild_id).try_read(),
+        },
+        None => match action {
+            Some(action) => keys.action_fees.get(action.into_byte()).try_read(),
+            None => None,
+        },
+    }
+}
+
+/// Returns the most specific override applying to a claim, i.e. that of the guild and action,
+/// then that of the guild, then that of the action
+#[storage(read)]
+pub fn _applied_fee_override(guild_id: u64, action: GuildAction, keys: FeeKeys) -> Option<u64> {
+    let action = action.into_byte();
+    if let Some(fee) = keys.guild_action_fees.get(guild_id).get(action).try_read() {
+        return Some(fee);
+    }
+    if let Some(fee) = keys.guild_fees.get(guild_id).try_read() {
+        return Some(fee);
+    }
+    keys.action_fees.get(action).try_read()
+}
+
+/// Returns the fee of a claim paid in the base asset
+///
+/// An override is an explicit price in the base asset, so it applies even if the base asset has
+/// been removed from the payment assets.
+#[storage(read)]
+pub fn _fee_for(
+    guild_id: u64,
+    action: GuildAction,
+    keys: FeeKeys,
+    payment_keys: PaymentKeys,
+) -> u64 {
+    match _applied_fee_override(guild_id, action, keys) {
+        Some(fee) => fee,
+        None => _fee(payment_keys),
+    }
+}
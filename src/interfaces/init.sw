@@ -1,5 +1,6 @@
 library;
 
+use ::interfaces::payment::{_write_payment_asset, PaymentKeys};
 use sway_libs::ownership::*;
 use standards::src5::State;
 
@@ -20,7 +21,7 @@ pub struct ContractInitialized {
 pub struct InitKeys {
     pub signer: StorageKey<b256>,
     pub treasury: StorageKey<Identity>,
-    pub fee: StorageKey<u64>,
+    pub payment: PaymentKeys,
 }
 
 abi Initialize {
@@ -38,7 +39,8 @@ pub fn _initialize(params: ContractInitialized, keys: InitKeys) {
     initialize_ownership(params.owner);
     keys.treasury.write(params.treasury);
     keys.signer.write(params.signer.into());
-    keys.fee.write(params.fee);
+    // the base asset is accepted by default
+    let _ = _write_payment_asset(AssetId::base(), params.fee, keys.payment);
     log(params);
 }
 
@@ -6,6 +6,7 @@ use ::common::pin::PinData;
 use ::common::utils::{push_str, str_to_bytes};
 use ::interfaces::token::TokenError;
 
+use standards::src7::Metadata;
 use std::hash::{Hash, Hasher};
 use std::string::String;
 
@@ -34,3 +35,51 @@ pub fn _encoded_metadata(pin_id: u64, key: StorageKey<StorageMap<u64, PinData>>)
 
     String::from(bytes)
 }
+
+/// SRC-7 metadata of a pin asset
+///
+/// Besides the whole json under `metadata`, every field of it is available under its own key
+/// following SRC-9, i.e. `name`, `description`, `image` and the attributes' trait types. Only role
+/// pins have a `roleId`.
+#[storage(read)]
+pub fn _asset_metadata(
+    pin_id: u64,
+    key: String,
+    metadata_key: StorageKey<StorageMap<u64, PinData>>,
+) -> Option<Metadata> {
+    let pin_data = match metadata_key.get(pin_id).try_read() {
+        Some(pin_data) => pin_data,
+        None => return None,
+    };
+
+    if key == String::from_ascii_str("metadata") {
+        Some(Metadata::String(pin_data.encode(pin_id)))
+    } else if key == String::from_ascii_str("name")
+        || key == String::from_ascii_str("type")
+    {
+        Some(Metadata::String(String::from_ascii_str(pin_data.action.to_str())))
+    } else if key == String::from_ascii_str("description") {
+        Some(Metadata::String(String::from(pin_data.description())))
+    } else if key == String::from_ascii_str("image") {
+        Some(Metadata::String(String::from(pin_data.image())))
+    } else if key == String::from_ascii_str("guildId") {
+        Some(Metadata::Int(pin_data.guild_id))
+    } else if key == String::from_ascii_str("roleId") {
+        match pin_data.action.role_id() {
+            Some(role_id) => Some(Metadata::Int(role_id)),
+            None => None,
+        }
+    } else if key == String::from_ascii_str("userId") {
+        Some(Metadata::Int(pin_data.user_id))
+    } else if key == String::from_ascii_str("rank") {
+        Some(Metadata::Int(pin_id))
+    } else if key == String::from_ascii_str("mintDate") {
+        Some(Metadata::Int(pin_data.mint_date))
+    } else if key == String::from_ascii_str("actionDate") {
+        Some(Metadata::Int(pin_data.created_at))
+    } else if key == String::from_ascii_str("status") {
+        Some(Metadata::String(String::from_ascii_str(pin_data.status())))
+    } else {
+        None
+    }
+}
@@ -1,9 +1,19 @@
 library;
 
+use ::common::pin::RevocationReason;
+use ::interfaces::payment::{_payment_fee, _write_payment_asset, PaymentError, PaymentKeys};
+use ::interfaces::src20::_pin_id_by_asset;
 use sway_libs::ownership::{only_owner, transfer_ownership};
 
+use std::asset::transfer;
+use std::context::this_balance;
 use std::vm::evm::evm_address::EvmAddress;
 
+pub enum OwnerError {
+    InsufficientBalance: (),
+    CannotWithdrawPin: (),
+}
+
 abi OnlyOwner {
     #[storage(read, write)]
     fn set_owner(owner: Identity);
@@ -13,6 +23,10 @@ abi OnlyOwner {
     fn set_treasury(treasury: Identity);
     #[storage(read, write)]
     fn set_fee(fee: u64);
+    #[storage(read)]
+    fn withdraw(asset: AssetId, amount: u64, to: Identity);
+    #[storage(read, write)]
+    fn revoke(pin_id: u64, reason: RevocationReason);
 }
 
 abi OwnerInfo {
@@ -44,6 +58,12 @@ pub struct FeeChanged {
     new: u64,
 }
 
+pub struct Withdrawn {
+    asset: AssetId,
+    amount: u64,
+    to: Identity,
+}
+
 #[storage(read, write)]
 pub fn _set_owner(new_owner: Identity) {
     transfer_ownership(new_owner);
@@ -71,17 +91,36 @@ pub fn _set_treasury(treasury: Identity, key: StorageKey<Identity>) {
     });
 }
 
+// NOTE the fee is the base asset's entry in the payment assets map
 #[storage(read, write)]
-pub fn _set_fee(fee: u64, key: StorageKey<u64>) {
+pub fn _set_fee(fee: u64, keys: PaymentKeys) {
     only_owner();
-    let old_fee = key.read();
-    key.write(fee);
+    let old_fee = _write_payment_asset(AssetId::base(), fee, keys);
     log(FeeChanged {
-        old: old_fee,
+        old: old_fee.unwrap_or(0),
         new: fee,
     });
 }
 
+#[storage(read)]
+pub fn _withdraw(
+    asset: AssetId,
+    amount: u64,
+    to: Identity,
+    pin_id_by_asset: StorageKey<StorageMap<AssetId, u64>>,
+) {
+    only_owner();
+    // pins in custody (soulbound mode) belong to their owners
+    require(
+        _pin_id_by_asset(asset, pin_id_by_asset)
+            .is_none(),
+        OwnerError::CannotWithdrawPin,
+    );
+    require(this_balance(asset) >= amount, OwnerError::InsufficientBalance);
+    transfer(to, asset, amount);
+    log(Withdrawn { asset, amount, to });
+}
+
 #[storage(read)]
 pub fn _signer(key: StorageKey<b256>) -> b256 {
     // NOTE cannot return EvmAddress, because it gets added to the abi as a () type
@@ -93,7 +132,14 @@ pub fn _treasury(key: StorageKey<Identity>) -> Identity {
     key.read()
 }
 
+// NOTE reverts if the base asset is no longer accepted, claims paying with it would revert anyway
 #[storage(read)]
-pub fn _fee(key: StorageKey<u64>) -> u64 {
-    key.read()
+pub fn _fee(keys: PaymentKeys) -> u64 {
+    match _payment_fee(AssetId::base(), keys) {
+        Some(fee) => fee,
+        None => {
+            require(false, PaymentError::AssetNotAccepted);
+            revert(0);
+        }
+    }
 }
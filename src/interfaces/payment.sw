@@ -0,0 +1,90 @@
+library;
+
+use sway_libs::ownership::only_owner;
+
+use std::storage::storage_vec::*;
+
+pub enum PaymentError {
+    AssetNotAccepted: (),
+}
+
+abi PaymentAssets {
+    #[storage(read, write)]
+    fn set_payment_asset(asset: AssetId, fee: u64);
+    #[storage(read, write)]
+    fn remove_payment_asset(asset: AssetId);
+    #[storage(read)]
+    fn payment_fee(asset: AssetId) -> Option<u64>;
+    #[storage(read)]
+    fn payment_assets() -> Vec<AssetId>;
+}
+
+pub struct PaymentAssetSet {
+    pub asset: AssetId,
+    pub old_fee: Option<u64>,
+    pub new_fee: u64,
+}
+
+pub struct PaymentAssetRemoved {
+    pub asset: AssetId,
+    pub fee: u64,
+}
+
+pub struct PaymentKeys {
+    pub fees: StorageKey<StorageMap<AssetId, u64>>,
+    pub assets: StorageKey<StorageVec<AssetId>>,
+}
+
+#[storage(read, write)]
+pub fn _set_payment_asset(asset: AssetId, fee: u64, keys: PaymentKeys) {
+    only_owner();
+    let old_fee = _write_payment_asset(asset, fee, keys);
+    log(PaymentAssetSet {
+        asset,
+        old_fee,
+        new_fee: fee,
+    });
+}
+
+#[storage(read, write)]
+pub fn _remove_payment_asset(asset: AssetId, keys: PaymentKeys) {
+    only_owner();
+    let fee = match keys.fees.get(asset).try_read() {
+        Some(fee) => fee,
+        None => {
+            require(false, PaymentError::AssetNotAccepted);
+            revert(0);
+        }
+    };
+    let _ = keys.fees.remove(asset);
+    let mut i = 0;
+    while i < keys.assets.len() {
+        if keys.assets.get(i).unwrap().read() == asset {
+            let _ = keys.assets.swap_remove(i);
+            break;
+        }
+        i += 1;
+    }
+    log(PaymentAssetRemoved { asset, fee });
+}
+
+/// Inserts or updates an accepted payment asset and returns its previous fee
+#[storage(read, write)]
+pub fn _write_payment_asset(asset: AssetId, fee: u64, keys: PaymentKeys) -> Option<u64> {
+    let old_fee = keys.fees.get(asset).try_read();
+    if old_fee.is_none() {
+        keys.assets.push(asset);
+    }
+    keys.fees.insert(asset, fee);
+    old_fee
+}
+
+#[storage(read)]
+pub fn _payment_fee(asset: AssetId, keys: PaymentKeys) -> Option<u64> {
+    keys.fees.get(asset).try_read()
+}
+
+#[storage(read)]
+pub fn _payment_assets(keys: PaymentKeys) -> Vec<AssetId> {
+    keys.assets.load_vec()
+}
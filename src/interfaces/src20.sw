@@ -4,37 +4,63 @@ use ::common::contract_id;
 use standards::src20::SRC20;
 use std::string::String;
 
-pub fn _total_assets() -> u64 {
-    1
+// NOTE every pin is a distinct asset, so the number of assets is the number of minted pins
+#[storage(read)]
+pub fn _total_assets(key: StorageKey<u64>) -> u64 {
+    key.read()
 }
 
 #[storage(read)]
-pub fn _total_supply(asset: AssetId, key: StorageKey<u64>) -> Option<u64> {
-    if asset == AssetId::default() {
-        Some(key.read())
-    } else {
-        None
+pub fn _pin_id_by_asset(asset: AssetId, key: StorageKey<StorageMap<AssetId, u64>>) -> Option<u64> {
+    key.get(asset).try_read()
+}
+
+#[storage(read)]
+pub fn _total_supply(
+    asset: AssetId,
+    key: StorageKey<StorageMap<AssetId, u64>>,
+    owners_key: StorageKey<StorageMap<u64, Option<Address>>>,
+) -> Option<u64> {
+    match _pin_id_by_asset(asset, key) {
+        Some(pin_id) => {
+            match owners_key.get(pin_id).try_read() {
+                Some(Some(_)) => Some(1),
+                _ => Some(0),
+            }
+        },
+        None => None,
     }
 }
 
-pub fn _name(asset: AssetId, const_name: str[9]) -> Option<String> {
-    if asset == AssetId::default() {
+#[storage(read)]
+pub fn _name(
+    asset: AssetId,
+    const_name: str[9],
+    key: StorageKey<StorageMap<AssetId, u64>>,
+) -> Option<String> {
+    if _pin_id_by_asset(asset, key).is_some() {
         Some(String::from_ascii_str(from_str_array(const_name)))
     } else {
         None
     }
 }
 
-pub fn _symbol(asset: AssetId, const_symbol: str[5]) -> Option<String> {
-    if asset == AssetId::default() {
+#[storage(read)]
+pub fn _symbol(
+    asset: AssetId,
+    const_symbol: str[5],
+    key: StorageKey<StorageMap<AssetId, u64>>,
+) -> Option<String> {
+    if _pin_id_by_asset(asset, key).is_some() {
         Some(String::from_ascii_str(from_str_array(const_symbol)))
     } else {
         None
     }
 }
 
-pub fn _decimals(asset: AssetId) -> Option<u8> {
-    if asset == AssetId::default() {
+#[storage(read)]
+pub fn _decimals(asset: AssetId, key: StorageKey<StorageMap<AssetId, u64>>) -> Option<u8> {
+    if _pin_id_by_asset(asset, key).is_some() {
         Some(0)
     } else {
         None
@@ -0,0 +1,66 @@
+library;
+
+use ::common::action::GuildAction;
+use ::common::pin::PinData;
+use ::common::{contract_id, pin_sub_id};
+use ::interfaces::init::_initialized;
+use ::interfaces::src20::_pin_id_by_asset;
+use ::interfaces::token::{_burn, _mint_pin, _unix_timestamp, TokenError, TokenKeys};
+use sway_libs::ownership::only_owner;
+
+/// Mints an airdropped pin, restricted to the owner.
+///
+/// Generic tooling has no means to provide guild data, so airdropped pins are not bound to any
+/// guild, i.e. they are not indexed by `pin_id_by_address` and `pin_id_by_user_id`. Every pin is a
+/// distinct asset, so `sub_id` has to be the sub id of the next pin and `amount` has to be 1.
+#[storage(read, write)]
+pub fn _mint(
+    recipient: Identity,
+    sub_id: SubId,
+    amount: u64,
+    soulbound: bool,
+    token_keys: TokenKeys,
+) {
+    only_owner();
+    _initialized();
+    let recipient = match recipient {
+        Identity::Address(address) => address,
+        Identity::ContractId(_) => {
+            require(false, TokenError::InvalidRecipient);
+            revert(0);
+        }
+    };
+    require(amount == 1, TokenError::InvalidAmount);
+    require(
+        sub_id == pin_sub_id(token_keys.total_minted.read()),
+        TokenError::InvalidSubId,
+    );
+
+    let timestamp = _unix_timestamp();
+    let empty = __to_str_array("                                                                ");
+    let metadata = PinData {
+        holder: recipient,
+        action: GuildAction::Joined,
+        user_id: 0,
+        guild_id: 0,
+        guild_name: empty,
+        created_at: timestamp,
+        cid: empty,
+        mint_date: timestamp,
+        revoked: false,
+    };
+    let _ = _mint_pin(metadata, soulbound, token_keys);
+}
+
+/// Burns the pin minted under `sub_id`, following the same rules as burning via the pin id.
+#[storage(read, write)]
+pub fn _burn_asset(sub_id: SubId, amount: u64, soulbound: bool, token_keys: TokenKeys) {
+    require(amount == 1, TokenError::InvalidAmount);
+    match _pin_id_by_asset(AssetId::new(contract_id(), sub_id), token_keys.pin_id_by_asset) {
+        Some(pin_id) => _burn(pin_id, soulbound, token_keys),
+        None => {
+            require(false, TokenError::PinIdDoesNotExist);
+            revert(0);
+        }
+    }
+}
@@ -2,16 +2,19 @@ library;
 
 use ::common::action::GuildAction;
 use ::common::claim::ClaimParameters;
-use ::common::pin::PinData;
-use ::common::contract_id;
+use ::common::pin::{PinData, RevocationReason};
+use ::common::update::UpdateParameters;
+use ::common::{contract_id, pin_asset_id, pin_sub_id};
+use ::interfaces::fees::{_applied_fee_override, FeeError, FeeKeys};
 use ::interfaces::init::{_initialized, InitKeys};
+use ::interfaces::payment::{_payment_fee, PaymentError};
+use sway_libs::ownership::only_owner;
 
 use std::b512::B512;
-use std::asset::{burn, mint_to, transfer};
+use std::asset::{burn, mint, mint_to, transfer};
 use std::asset_id::AssetId;
 use std::block::timestamp as now;
 use std::call_frames::msg_asset_id;
-use std::constants::ZERO_B256;
 use std::context::msg_amount;
 use std::hash::Hash;
 use std::vm::evm::ecr::ec_recover_evm_address;
@@ -24,10 +27,17 @@ pub enum TokenError {
     InvalidSignature: (),
     InvalidAssetId: (),
     InvalidContractId: (),
+    InvalidChainId: (),
+    MessageAlreadyConsumed: (),
     InsufficientAmount: (),
     PinIdDoesNotExist: (),
     NotPinOwner: (),
     CouldNotRemoveEntry: (),
+    InvalidAmount: (),
+    InvalidSubId: (),
+    InvalidRecipient: (),
+    AlreadyRevoked: (),
+    StaleUpdate: (),
 }
 
 pub struct PinMinted {
@@ -40,6 +50,18 @@ pub struct PinBurned {
     pub pin_id: u64,
 }
 
+pub struct PinMetadataUpdated {
+    pub pin_id: u64,
+    pub guild_name: str[64],
+    pub cid: str[64],
+}
+
+pub struct PinRevoked {
+    pub pin_owner: Address,
+    pub pin_id: u64,
+    pub reason: RevocationReason,
+}
+
 // NOTE can't use type aliases here either because the compiler can't find the respective methods
 // for type aliases
 pub struct TokenKeys {
@@ -52,14 +74,18 @@ pub struct TokenKeys {
     pub total_minted: StorageKey<u64>,
     pub total_supply: StorageKey<u64>,
     pub token_of_address_by_index: StorageKey<StorageMap<Address, StorageMap<u64, u64>>>,
+    pub admin_fees_per_guild: StorageKey<StorageMap<u64, StorageMap<AssetId, u64>>>,
+    pub consumed_messages: StorageKey<StorageMap<b256, bool>>,
+    pub pin_id_by_asset: StorageKey<StorageMap<AssetId, u64>>,
 }
 
 abi PinToken {
+    /// Returns the amount refunded to the sender in case of an overpayment.
     #[payable]
     #[storage(read, write)]
-    fn claim(params: ClaimParameters, signature: B512);
+    fn claim(params: ClaimParameters, signature: B512) -> u64;
     #[storage(read, write)]
-    fn burn(pin_id: u64);
+    fn update_pin_metadata(params: UpdateParameters, signature: B512);
 }
 
 abi PinInfo {
@@ -68,8 +94,13 @@ abi PinInfo {
     #[storage(read)]
     fn pin_owner(pin_id: u64) -> Option<Address>;
     #[storage(read)]
+    fn pin_owners(start: u64, count: u64) -> Vec<Option<Address>>;
+    #[storage(read)]
     fn total_minted() -> u64;
     #[storage(read)]
+    fn circulating_supply() -> u64;
+    fn soulbound() -> bool;
+    #[storage(read)]
     fn total_minted_per_guild(guild_id: u64) -> u64;
     #[storage(read)]
     fn pin_id_by_address(user: Address, guild_id: u64, action: GuildAction) -> Option<u64>;
@@ -77,6 +108,10 @@ abi PinInfo {
     fn pin_id_by_user_id(user_id: u64, guils_id: u64, action: GuildAction) -> Option<u64>;
     #[storage(read)]
     fn token_of_owner_by_index(user: Address, index: u64) -> Option<u64>;
+    #[storage(read)]
+    fn admin_fees_per_guild(guild_id: u64, asset: AssetId) -> u64;
+    #[storage(read)]
+    fn message_consumed(message: b256) -> bool;
 }
 
 #[storage(read, write)]
@@ -84,14 +119,35 @@ pub fn _claim(
     params: ClaimParameters,
     signature: B512,
     signature_validity_period: u64,
+    chain_id: u64,
+    soulbound: bool,
     token_keys: TokenKeys,
     init_keys: InitKeys,
-) {
+    fee_keys: FeeKeys,
+) -> u64 {
     // NOTE anyone call this function if they have the params with a valid signature
     // check if the contract is initialized
     _initialized();
     // perform checks
-    let mint_date = _check_signature(params, signature, signature_validity_period, init_keys);
+    let message = params.to_message();
+    let mint_date = _check_signature(
+        params
+            .contract_id,
+        params
+            .chain_id,
+        params
+            .signed_at,
+        message,
+        signature,
+        signature_validity_period,
+        chain_id,
+        init_keys,
+    );
+    // a signed message can only be used once, even if the pin is burned in the meantime
+    require(
+        !_message_consumed(message, token_keys.consumed_messages),
+        TokenError::MessageAlreadyConsumed,
+    );
     require(
         !(_pin_id_by_address(
                 params
@@ -117,35 +173,53 @@ pub fn _claim(
         TokenError::AlreadyClaimed,
     );
 
-    // collect fees in ETH
-    let fee = init_keys.fee.read();
+    // collect fees in any of the accepted payment assets, except for negotiated guild and action
+    // fees, which are priced in the base asset
     let asset_id = msg_asset_id();
-    require(asset_id == AssetId::base(), TokenError::InvalidAssetId);
+    let fee = match _applied_fee_override(params.guild_id, params.action, fee_keys) {
+        Some(fee) => {
+            require(asset_id == AssetId::base(), FeeError::BaseAssetRequired);
+            fee
+        },
+        None => match _payment_fee(asset_id, init_keys.payment) {
+            Some(fee) => fee,
+            None => {
+                require(false, PaymentError::AssetNotAccepted);
+                revert(0);
+            }
+        },
+    };
+    let amount = msg_amount();
     require(
-        msg_amount() >= params
+        amount >= params
             .admin_fee + fee,
         TokenError::InsufficientAmount,
     );
 
-    // Note temporarily disabled admin fee collection
-    // if params.admin_treasury != Identity::ContractId(contract_id())
-    // {
-    //     transfer(params.admin_treasury, asset_id, params.admin_fee);
-    // }
+    // forward the admin fee unless the admin treasury is this contract
+    if params.admin_fee > 0
+        && params.admin_treasury != Identity::ContractId(contract_id())
+    {
+        transfer(params.admin_treasury, asset_id, params.admin_fee);
+        let admin_fees_key = token_keys.admin_fees_per_guild.get(params.guild_id).get(asset_id);
+        admin_fees_key.write(admin_fees_key.try_read().unwrap_or(0) + params.admin_fee);
+    }
     transfer(init_keys.treasury.read(), asset_id, fee);
 
+    // refund the surplus, e.g. when the fee was lowered after the amount was quoted
+    let refund = amount - params.admin_fee - fee;
+    if refund > 0 {
+        let refund_to = match msg_sender() {
+            Ok(sender) => sender,
+            Err(_) => Identity::Address(params.recipient),
+        };
+        transfer(refund_to, asset_id, refund);
+    }
+
     // update storage
+    token_keys.consumed_messages.insert(message, true);
     let pin_id = token_keys.total_minted.read();
-    let balance = _balance_of(params.recipient, token_keys.balances);
     let total_minted_per_guild = token_keys.total_minted_per_guild.get(params.guild_id).try_read().unwrap_or(0);
-    token_keys.balances.insert(params.recipient, balance + 1);
-    token_keys.pin_owners.insert(pin_id, Some(params.recipient));
-    token_keys
-        .total_minted
-        .write(token_keys.total_minted.read() + 1);
-    token_keys
-        .total_supply
-        .write(token_keys.total_supply.read() + 1);
     token_keys
         .total_minted_per_guild
         .insert(params.guild_id, total_minted_per_guild + 1);
@@ -157,7 +231,6 @@ pub fn _claim(
         .token_id_by_user_id
         .insert(params.user_id, claims_map_key);
 
-    // persist token metadata
     let metadata = PinData {
         holder: params.recipient,
         action: params.action,
@@ -167,32 +240,59 @@ pub fn _claim(
         created_at: params.created_at,
         cid: params.cid,
         mint_date,
+        revoked: false,
     };
+    let _ = _mint_pin(metadata, soulbound, token_keys);
+
+    refund
+}
+
+/// Records a new pin for `metadata.holder` and mints its asset, returns the new pin's id.
+///
+/// Guild specific indexes are not touched, those are up to the caller.
+#[storage(read, write)]
+pub fn _mint_pin(metadata: PinData, soulbound: bool, token_keys: TokenKeys) -> u64 {
+    let recipient = metadata.holder;
+    let pin_id = token_keys.total_minted.read();
+    let balance = _balance_of(recipient, token_keys.balances);
+    token_keys.balances.insert(recipient, balance + 1);
+    token_keys.pin_owners.insert(pin_id, Some(recipient));
+    token_keys.total_minted.write(pin_id + 1);
+    token_keys
+        .total_supply
+        .write(token_keys.total_supply.read() + 1);
+
+    // persist token metadata
     token_keys.metadata.insert(pin_id, metadata);
 
-    let user_index_map_key = token_keys.token_of_address_by_index.get(params.recipient);
+    let user_index_map_key = token_keys.token_of_address_by_index.get(recipient);
     user_index_map_key.insert(balance, pin_id);
 
     // mint token
-    mint_to(Identity::Address(params.recipient), ZERO_B256, 1);
+    token_keys.pin_id_by_asset.insert(pin_asset_id(pin_id), pin_id);
+    // soulbound pins are kept by the contract, ownership is only recorded in storage
+    if soulbound {
+        mint(pin_sub_id(pin_id), 1);
+    } else {
+        mint_to(Identity::Address(recipient), pin_sub_id(pin_id), 1);
+    }
     log(PinMinted {
-        recipient: params.recipient,
+        recipient,
         pin_id,
     });
+
+    pin_id
 }
 
+/// Burns a pin, either by its holder sending the pin token along, or in soulbound mode by its
+/// recorded owner.
+///
+/// Pins can be transferred unless soulbound, so the holder is not necessarily the recorded owner.
+/// The recorded owner's balance and index are updated either way.
 #[storage(read, write)]
-pub fn _burn(pin_id: u64, token_keys: TokenKeys) {
-    // check ownership
+pub fn _burn(pin_id: u64, soulbound: bool, token_keys: TokenKeys) {
     let pin_owner = match token_keys.pin_owners.get(pin_id).try_read() {
-        Some(Some(pin_owner)) => {
-            require(
-                msg_sender()
-                    .unwrap() == Identity::Address(pin_owner),
-                TokenError::NotPinOwner,
-            );
-            pin_owner
-        },
+        Some(Some(pin_owner)) => pin_owner,
         Some(None) => {
             require(false, TokenError::AlreadyBurned);
             revert(0);
@@ -203,6 +303,22 @@ pub fn _burn(pin_id: u64, token_keys: TokenKeys) {
         }
     };
 
+    if soulbound {
+        // the contract has custody over the pin token, so only the recorded owner may burn it
+        require(
+            msg_sender()
+                .unwrap() == Identity::Address(pin_owner),
+            TokenError::NotPinOwner,
+        );
+    } else {
+        // there's a single pin token, so sending it along proves that the caller holds the pin
+        require(
+            msg_asset_id() == pin_asset_id(pin_id),
+            TokenError::InvalidAssetId,
+        );
+        require(msg_amount() == 1, TokenError::InsufficientAmount);
+    }
+
     // update storage
     let metadata = token_keys.metadata.get(pin_id).read();
     let balance = token_keys.balances.get(pin_owner).read();
@@ -213,47 +329,180 @@ pub fn _burn(pin_id: u64, token_keys: TokenKeys) {
     let removed = token_keys.metadata.remove(pin_id);
     require(removed, TokenError::CouldNotRemoveEntry);
     token_keys.pin_owners.insert(pin_id, None);
-    let removed = token_keys.token_id_by_address.get(pin_owner).get(metadata.guild_id).remove(metadata.action);
+    _unindex(pin_id, pin_owner, metadata, token_keys);
+
+    // compact the owner's index by moving the last pin into the burned pin's slot
+    let index_key = token_keys.token_of_address_by_index.get(pin_owner);
+    let last_index = balance - 1;
+    let mut index = 0;
+    while index < last_index {
+        if index_key.get(index).read() == pin_id {
+            index_key.insert(index, index_key.get(last_index).read());
+            break;
+        }
+        index += 1;
+    }
+    let removed = index_key.remove(last_index);
     require(removed, TokenError::CouldNotRemoveEntry);
 
     // burn token
-    burn(ZERO_B256, 1);
+    burn(pin_sub_id(pin_id), 1);
     log(PinBurned {
         pin_owner,
         pin_id,
     });
 }
 
+/// Replaces the guild name and artwork of a pin with the backend-signed values.
+///
+/// Updates of a pin are only applied in signing order, i.e. an update signed before the last
+/// applied one reverts.
+#[storage(read, write)]
+pub fn _update_pin_metadata(
+    params: UpdateParameters,
+    signature: B512,
+    signature_validity_period: u64,
+    chain_id: u64,
+    token_keys: TokenKeys,
+    init_keys: InitKeys,
+    last_update: StorageKey<StorageMap<u64, u64>>,
+) {
+    _initialized();
+    let message = params.to_message();
+    let _ = _check_signature(
+        params
+            .contract_id,
+        params
+            .chain_id,
+        params
+            .signed_at,
+        message,
+        signature,
+        signature_validity_period,
+        chain_id,
+        init_keys,
+    );
+    require(
+        !_message_consumed(message, token_keys.consumed_messages),
+        TokenError::MessageAlreadyConsumed,
+    );
+
+    let mut metadata = match token_keys.metadata.get(params.pin_id).try_read() {
+        Some(metadata) => metadata,
+        None => {
+            // burned pins have no metadata either
+            require(false, TokenError::PinIdDoesNotExist);
+            revert(0);
+        }
+    };
+    // updates may be submitted out of order, an older one must not overwrite a newer one
+    if let Some(last_signed_at) = last_update.get(params.pin_id).try_read() {
+        require(params.signed_at > last_signed_at, TokenError::StaleUpdate);
+    }
+    metadata.guild_name = params.guild_name;
+    metadata.cid = params.cid;
+    token_keys.metadata.insert(params.pin_id, metadata);
+    token_keys.consumed_messages.insert(message, true);
+    last_update.insert(params.pin_id, params.signed_at);
+
+    log(PinMetadataUpdated {
+        pin_id: params.pin_id,
+        guild_name: params.guild_name,
+        cid: params.cid,
+    });
+}
+
+/// Marks a pin revoked, restricted to the owner.
+///
+/// The pin stays with its owner, but it no longer counts as a claim, so it can be claimed again
+/// with a new signature, e.g. when the user rejoins the guild.
+#[storage(read, write)]
+pub fn _revoke(pin_id: u64, reason: RevocationReason, token_keys: TokenKeys) {
+    only_owner();
+    let pin_owner = match token_keys.pin_owners.get(pin_id).try_read() {
+        Some(Some(pin_owner)) => pin_owner,
+        Some(None) => {
+            require(false, TokenError::AlreadyBurned);
+            revert(0);
+        },
+        None => {
+            require(false, TokenError::PinIdDoesNotExist);
+            revert(0);
+        }
+    };
+
+    let mut metadata = token_keys.metadata.get(pin_id).read();
+    require(!metadata.revoked, TokenError::AlreadyRevoked);
+    metadata.revoked = true;
+    token_keys.metadata.insert(pin_id, metadata);
+    _unindex(pin_id, pin_owner, metadata, token_keys);
+
+    log(PinRevoked {
+        pin_owner,
+        pin_id,
+        reason,
+    });
+}
+
+/// Removes the pin from the guild specific indexes.
+#[storage(read, write)]
+fn _unindex(pin_id: u64, pin_owner: Address, metadata: PinData, token_keys: TokenKeys) {
+    // airdropped and revoked pins are not indexed by guild, so only remove entries pointing to
+    // this pin
+    let address_claims_key = token_keys.token_id_by_address.get(pin_owner).get(metadata.guild_id);
+    let address_claim = address_claims_key.get(metadata.action).try_read();
+    if address_claim.is_some() && address_claim.unwrap() == pin_id {
+        let _ = address_claims_key.remove(metadata.action);
+    }
+
+    // the user id points to the claims map of the address it last claimed with, which is not
+    // necessarily the current pin owner's map
+    if let Some(claims_map_key) = token_keys.token_id_by_user_id.get(metadata.user_id).try_read() {
+        let claimed_key = claims_map_key.get(metadata.guild_id);
+        let claimed = claimed_key.get(metadata.action).try_read();
+        if claimed.is_some() && claimed.unwrap() == pin_id {
+            let _ = claimed_key.remove(metadata.action);
+        }
+    }
+}
+
+/// Checks a backend signature over `message`, returns the current unix timestamp.
 #[storage(read)]
 fn _check_signature(
-    params: ClaimParameters,
+    signed_contract_id: ContractId,
+    signed_chain_id: u64,
+    signed_at: u64,
+    message: b256,
     signature: B512,
     signature_validity_period: u64,
+    chain_id: u64,
     init_keys: InitKeys,
 ) -> u64 {
     require(
-        params
-            .contract_id == contract_id(),
+        signed_contract_id == contract_id(),
         TokenError::InvalidContractId,
     );
-    // convert from tai64 to unix timestamp
-    let timestamp = now() - (1 << 62) - 10;
+    require(signed_chain_id == chain_id, TokenError::InvalidChainId);
+    let timestamp = _unix_timestamp();
     // check signature expiration
     require(
-        params
-            .signed_at > timestamp - signature_validity_period,
+        signed_at > timestamp - signature_validity_period,
         TokenError::ExpiredSignature,
     );
 
     // check signature validity
     let signer = EvmAddress::from(init_keys.signer.read());
-    let message = params.to_message();
     let recovered = ec_recover_evm_address(signature, message).unwrap();
 
     require(signer == recovered, TokenError::InvalidSignature);
     timestamp
 }
 
+/// Current block time converted from tai64 to a unix timestamp.
+pub fn _unix_timestamp() -> u64 {
+    now() - (1 << 62) - 10
+}
+
 // NOTE unfortunately I need to explicitly write out the map type, otherwise the compiler cries
 // that there's no method `get` found for `StorageKey<TokenIdByAddressMap>`
 #[storage(read)]
@@ -293,11 +542,42 @@ pub fn _pin_owner(
     key.get(pin_id).try_read().unwrap_or(None)
 }
 
+/// Returns the owners of at most `count` pins from `start` on, burned pins have no owner
+#[storage(read)]
+pub fn _pin_owners(
+    start: u64,
+    count: u64,
+    total_minted: u64,
+    key: StorageKey<StorageMap<u64, Option<Address>>>,
+) -> Vec<Option<Address>> {
+    let mut owners = Vec::new();
+    let mut pin_id = start;
+    while pin_id < total_minted && pin_id - start < count {
+        owners.push(_pin_owner(pin_id, key));
+        pin_id += 1;
+    }
+    owners
+}
+
 #[storage(read)]
 pub fn _total_minted_per_guild(guild_id: u64, key: StorageKey<StorageMap<u64, u64>>) -> u64 {
     key.get(guild_id).try_read().unwrap_or(0)
 }
 
+#[storage(read)]
+pub fn _admin_fees_per_guild(
+    guild_id: u64,
+    asset: AssetId,
+    key: StorageKey<StorageMap<u64, StorageMap<AssetId, u64>>>,
+) -> u64 {
+    key.get(guild_id).get(asset).try_read().unwrap_or(0)
+}
+
+#[storage(read)]
+pub fn _message_consumed(message: b256, key: StorageKey<StorageMap<b256, bool>>) -> bool {
+    key.get(message).try_read().unwrap_or(false)
+}
+
 #[storage(read)]
 pub fn _token_of_owner_by_index(
     user: Address,
@@ -5,22 +5,27 @@ mod interfaces;
 
 use ::common::action::GuildAction;
 use ::common::claim::ClaimParameters;
-use ::common::pin::PinData;
-use ::common::utils::parse_u64;
+use ::common::pin::{PinData, RevocationReason};
+use ::common::update::UpdateParameters;
 use ::common::*;
+use ::interfaces::fees::*;
 use ::interfaces::init::*;
 use ::interfaces::metadata::*;
 use ::interfaces::owner::*;
+use ::interfaces::payment::*;
 use ::interfaces::src20::*;
+use ::interfaces::src3::*;
 use ::interfaces::token::*;
 use sway_libs::ownership::*;
 use standards::src20::SRC20;
+use standards::src3::SRC3;
 use standards::src5::{SRC5, State};
 use standards::src7::{Metadata, SRC7};
 
 use std::b512::B512;
 use std::constants::ZERO_B256;
 use std::hash::Hash;
+use std::storage::storage_vec::*;
 use std::string::String;
 use std::vm::evm::evm_address::EvmAddress;
 
@@ -30,6 +35,9 @@ configurable {
     OWNER: Identity = Identity::Address(Address::from(ZERO_B256)),
     SIGNER: b256 = ZERO_B256,
     SIGNATURE_VALIDITY_PERIOD: u64 = 3600,
+    CHAIN_ID: u64 = 0,
+    /// If set, minted pins stay in the contract's custody, so they can't be transferred
+    SOULBOUND: bool = false,
     TREASURY: Identity = Identity::ContractId(ContractId::from(ZERO_B256)),
     FEE: u64 = 0,
 }
@@ -39,8 +47,10 @@ storage {
     signer: b256 = ZERO_B256,
     /// Treasury address receiving minting fees
     treasury: Identity = Identity::Address(Address::from(ZERO_B256)),
-    /// Fee collected upon claiming a pin
-    fee: u64 = 0,
+    /// Map: accepted payment asset -> fee collected upon claiming a pin
+    payment_fees: StorageMap<AssetId, u64> = StorageMap {},
+    /// List of accepted payment assets
+    payment_assets: StorageVec<AssetId> = StorageVec {},
     /// Map: pin_id -> metadata
     metadata: StorageMap<u64, PinData> = StorageMap {},
     /// Map: address -> pin_balance (increment upon claim, decrement upon burn)
@@ -55,12 +65,24 @@ storage {
     total_minted_per_guild: TotalMintedPerGuildMap = StorageMap {},
     /// Map: (address + token index) -> pin_id
     token_of_owner_by_index: TokenOfOwnerByIndexMap = StorageMap {},
+    /// Map: (guild_id + payment asset) -> admin fees forwarded to admin treasuries
+    admin_fees_per_guild: AdminFeesPerGuildMap = StorageMap {},
+    /// Map: signed claim message -> consumed
+    consumed_messages: ConsumedMessagesMap = StorageMap {},
+    /// Map: pin_id -> signed_at of the last applied metadata update
+    last_update: LastUpdateMap = StorageMap {},
     /// Only incremented
     total_minted: u64 = 0,
     /// Incremented upon successful claim, decremented upon successful burn
     total_supply: u64 = 0,
-    /// Dummy key to make warnings disappear
-    warning: bool = false,
+    /// Map: pin asset -> pin_id
+    pin_id_by_asset: PinIdByAssetMap = StorageMap {},
+    /// Map: (guild_id + guild_action byte) -> fee overriding the global fee
+    fees_per_guild_action: FeesPerGuildActionMap = StorageMap {},
+    /// Map: guild_id -> fee overriding the global fee
+    fees_per_guild: FeesPerGuildMap = StorageMap {},
+    /// Map: guild_action byte -> fee overriding the global fee
+    fees_per_action: FeesPerActionMap = StorageMap {},
 }
 
 impl Initialize for Contract {
@@ -75,7 +97,10 @@ impl Initialize for Contract {
         let keys = InitKeys {
             signer: storage.signer,
             treasury: storage.treasury,
-            fee: storage.fee,
+            payment: PaymentKeys {
+                fees: storage.payment_fees,
+                assets: storage.payment_assets,
+            },
         };
         _initialize(params, keys);
     }
@@ -96,7 +121,33 @@ impl OnlyOwner for Contract {
     }
     #[storage(read, write)]
     fn set_fee(fee: u64) {
-        _set_fee(fee, storage.fee)
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _set_fee(fee, keys)
+    }
+    #[storage(read)]
+    fn withdraw(asset: AssetId, amount: u64, to: Identity) {
+        _withdraw(asset, amount, to, storage.pin_id_by_asset)
+    }
+    #[storage(read, write)]
+    fn revoke(pin_id: u64, reason: RevocationReason) {
+        let token_keys = TokenKeys {
+            metadata: storage.metadata,
+            balances: storage.balances,
+            pin_owners: storage.pin_owners,
+            token_id_by_address: storage.token_id_by_address,
+            token_id_by_user_id: storage.token_id_by_user_id,
+            total_minted_per_guild: storage.total_minted_per_guild,
+            total_minted: storage.total_minted,
+            total_supply: storage.total_supply,
+            token_of_address_by_index: storage.token_of_owner_by_index,
+            admin_fees_per_guild: storage.admin_fees_per_guild,
+            consumed_messages: storage.consumed_messages,
+            pin_id_by_asset: storage.pin_id_by_asset,
+        };
+        _revoke(pin_id, reason, token_keys)
     }
 }
 
@@ -111,14 +162,87 @@ impl OwnerInfo for Contract {
     }
     #[storage(read)]
     fn fee() -> u64 {
-        _fee(storage.fee)
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _fee(keys)
+    }
+}
+
+impl PaymentAssets for Contract {
+    #[storage(read, write)]
+    fn set_payment_asset(asset: AssetId, fee: u64) {
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _set_payment_asset(asset, fee, keys)
+    }
+    #[storage(read, write)]
+    fn remove_payment_asset(asset: AssetId) {
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _remove_payment_asset(asset, keys)
+    }
+    #[storage(read)]
+    fn payment_fee(asset: AssetId) -> Option<u64> {
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _payment_fee(asset, keys)
+    }
+    #[storage(read)]
+    fn payment_assets() -> Vec<AssetId> {
+        let keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _payment_assets(keys)
+    }
+}
+
+impl FeeSchedule for Contract {
+    #[storage(read, write)]
+    fn set_fee_override(guild_id: Option<u64>, action: Option<GuildAction>, fee: Option<u64>) {
+        let keys = FeeKeys {
+            guild_action_fees: storage.fees_per_guild_action,
+            guild_fees: storage.fees_per_guild,
+            action_fees: storage.fees_per_action,
+        };
+        _set_fee_override(guild_id, action, fee, keys)
+    }
+    #[storage(read)]
+    fn fee_override(guild_id: Option<u64>, action: Option<GuildAction>) -> Option<u64> {
+        let keys = FeeKeys {
+            guild_action_fees: storage.fees_per_guild_action,
+            guild_fees: storage.fees_per_guild,
+            action_fees: storage.fees_per_action,
+        };
+        _fee_override(guild_id, action, keys)
+    }
+    #[storage(read)]
+    fn fee_for(guild_id: u64, action: GuildAction) -> u64 {
+        let keys = FeeKeys {
+            guild_action_fees: storage.fees_per_guild_action,
+            guild_fees: storage.fees_per_guild,
+            action_fees: storage.fees_per_action,
+        };
+        let payment_keys = PaymentKeys {
+            fees: storage.payment_fees,
+            assets: storage.payment_assets,
+        };
+        _fee_for(guild_id, action, keys, payment_keys)
     }
 }
 
 impl PinToken for Contract {
     #[payable]
     #[storage(read, write)]
-    fn claim(params: ClaimParameters, signature: B512) {
+    fn claim(params: ClaimParameters, signature: B512) -> u64 {
         let token_keys = TokenKeys {
             metadata: storage.metadata,
             balances: storage.balances,
@@ -129,38 +253,70 @@ impl PinToken for Contract {
             total_minted: storage.total_minted,
             total_supply: storage.total_supply,
             token_of_address_by_index: storage.token_of_owner_by_index,
+            admin_fees_per_guild: storage.admin_fees_per_guild,
+            consumed_messages: storage.consumed_messages,
+            pin_id_by_asset: storage.pin_id_by_asset,
         };
 
         let init_keys = InitKeys {
             signer: storage.signer,
             treasury: storage.treasury,
-            fee: storage.fee,
+            payment: PaymentKeys {
+                fees: storage.payment_fees,
+                assets: storage.payment_assets,
+            },
+        };
+        let fee_keys = FeeKeys {
+            guild_action_fees: storage.fees_per_guild_action,
+            guild_fees: storage.fees_per_guild,
+            action_fees: storage.fees_per_action,
         };
         _claim(
             params,
             signature,
             SIGNATURE_VALIDITY_PERIOD,
+            CHAIN_ID,
+            SOULBOUND,
             token_keys,
             init_keys,
-        );
+            fee_keys,
+        )
     }
 
     #[storage(read, write)]
-    fn burn(_pin_id: u64) {
-        // NOTE temporarily removed
-        //let token_keys = TokenKeys {
-        //    metadata: storage.metadata,
-        //    balances: storage.balances,
-        //    pin_owners: storage.pin_owners,
-        //    token_id_by_address: storage.token_id_by_address,
-        //    token_id_by_user_id: storage.token_id_by_user_id,
-        //    total_minted_per_guild: storage.total_minted_per_guild,
-        //    total_minted: storage.total_minted,
-        //    total_supply: storage.total_supply,
-        //    token_of_address_by_index: storage.token_of_address_by_index,
-        //};
-        //_burn(pin_id, token_keys)
-        log("burning tokens is not allowed");
+    fn update_pin_metadata(params: UpdateParameters, signature: B512) {
+        let token_keys = TokenKeys {
+            metadata: storage.metadata,
+            balances: storage.balances,
+            pin_owners: storage.pin_owners,
+            token_id_by_address: storage.token_id_by_address,
+            token_id_by_user_id: storage.token_id_by_user_id,
+            total_minted_per_guild: storage.total_minted_per_guild,
+            total_minted: storage.total_minted,
+            total_supply: storage.total_supply,
+            token_of_address_by_index: storage.token_of_owner_by_index,
+            admin_fees_per_guild: storage.admin_fees_per_guild,
+            consumed_messages: storage.consumed_messages,
+            pin_id_by_asset: storage.pin_id_by_asset,
+        };
+
+        let init_keys = InitKeys {
+            signer: storage.signer,
+            treasury: storage.treasury,
+            payment: PaymentKeys {
+                fees: storage.payment_fees,
+                assets: storage.payment_assets,
+            },
+        };
+        _update_pin_metadata(
+            params,
+            signature,
+            SIGNATURE_VALIDITY_PERIOD,
+            CHAIN_ID,
+            token_keys,
+            init_keys,
+            storage.last_update,
+        )
     }
 }
 
@@ -174,10 +330,28 @@ impl PinInfo for Contract {
         _pin_owner(pin_id, storage.pin_owners)
     }
     #[storage(read)]
+    fn pin_owners(start: u64, count: u64) -> Vec<Option<Address>> {
+        _pin_owners(
+            start,
+            count,
+            storage.total_minted.read(),
+            storage.pin_owners,
+        )
+    }
+    #[storage(read)]
     fn total_minted() -> u64 {
         storage.total_minted.read()
     }
 
+    #[storage(read)]
+    fn circulating_supply() -> u64 {
+        storage.total_supply.read()
+    }
+
+    fn soulbound() -> bool {
+        SOULBOUND
+    }
+
     #[storage(read)]
     fn total_minted_per_guild(guild_id: u64) -> u64 {
         _total_minted_per_guild(guild_id, storage.total_minted_per_guild)
@@ -197,6 +371,16 @@ impl PinInfo for Contract {
     fn token_of_owner_by_index(user: Address, index: u64) -> Option<u64> {
         _token_of_owner_by_index(user, index, storage.token_of_owner_by_index)
     }
+
+    #[storage(read)]
+    fn admin_fees_per_guild(guild_id: u64, asset: AssetId) -> u64 {
+        _admin_fees_per_guild(guild_id, asset, storage.admin_fees_per_guild)
+    }
+
+    #[storage(read)]
+    fn message_consumed(message: b256) -> bool {
+        _message_consumed(message, storage.consumed_messages)
+    }
 }
 
 impl SRC5 for Contract {
@@ -209,46 +393,79 @@ impl SRC5 for Contract {
 impl SRC20 for Contract {
     #[storage(read)]
     fn total_assets() -> u64 {
-        let _ = storage.warning.read();
-        _total_assets()
+        _total_assets(storage.total_minted)
     }
 
     #[storage(read)]
     fn total_supply(asset: AssetId) -> Option<u64> {
-        _total_supply(asset, storage.total_supply)
+        _total_supply(asset, storage.pin_id_by_asset, storage.pin_owners)
     }
 
     #[storage(read)]
     fn name(asset: AssetId) -> Option<String> {
-        let _ = storage.warning.read();
-        _name(asset, NAME)
+        _name(asset, NAME, storage.pin_id_by_asset)
     }
 
     #[storage(read)]
     fn symbol(asset: AssetId) -> Option<String> {
-        let _ = storage.warning.read();
-        _symbol(asset, SYMBOL)
+        _symbol(asset, SYMBOL, storage.pin_id_by_asset)
     }
 
     #[storage(read)]
     fn decimals(asset: AssetId) -> Option<u8> {
-        let _ = storage.warning.read();
-        _decimals(asset)
+        _decimals(asset, storage.pin_id_by_asset)
+    }
+}
+
+impl SRC3 for Contract {
+    #[storage(read, write)]
+    fn mint(recipient: Identity, sub_id: SubId, amount: u64) {
+        let token_keys = TokenKeys {
+            metadata: storage.metadata,
+            balances: storage.balances,
+            pin_owners: storage.pin_owners,
+            token_id_by_address: storage.token_id_by_address,
+            token_id_by_user_id: storage.token_id_by_user_id,
+            total_minted_per_guild: storage.total_minted_per_guild,
+            total_minted: storage.total_minted,
+            total_supply: storage.total_supply,
+            token_of_address_by_index: storage.token_of_owner_by_index,
+            admin_fees_per_guild: storage.admin_fees_per_guild,
+            consumed_messages: storage.consumed_messages,
+            pin_id_by_asset: storage.pin_id_by_asset,
+        };
+        _mint(recipient, sub_id, amount, SOULBOUND, token_keys)
+    }
+
+    #[payable]
+    #[storage(read, write)]
+    fn burn(sub_id: SubId, amount: u64) {
+        let token_keys = TokenKeys {
+            metadata: storage.metadata,
+            balances: storage.balances,
+            pin_owners: storage.pin_owners,
+            token_id_by_address: storage.token_id_by_address,
+            token_id_by_user_id: storage.token_id_by_user_id,
+            total_minted_per_guild: storage.total_minted_per_guild,
+            total_minted: storage.total_minted,
+            total_supply: storage.total_supply,
+            token_of_address_by_index: storage.token_of_owner_by_index,
+            admin_fees_per_guild: storage.admin_fees_per_guild,
+            consumed_messages: storage.consumed_messages,
+            pin_id_by_asset: storage.pin_id_by_asset,
+        };
+        _burn_asset(sub_id, amount, SOULBOUND, token_keys)
     }
 }
 
 impl SRC7 for Contract {
     #[storage(read)]
-    fn metadata(asset_id: AssetId, key: String) -> Option<Metadata> {
-        if asset_id != AssetId::default() {
-            None
+    fn metadata(asset: AssetId, key: String) -> Option<Metadata> {
+        // damn there's no `map` on Option<T>
+        if let Some(pin_id) = _pin_id_by_asset(asset, storage.pin_id_by_asset) {
+            _asset_metadata(pin_id, key, storage.metadata)
         } else {
-            // damn there's no `map` on Option<T>
-            if let Some(pin_id) = parse_u64(key) {
-                Some(Metadata::String(_metadata(pin_id, storage.metadata)))
-            } else {
-                None
-            }
+            None
         }
     }
 }
@@ -1,8 +1,10 @@
 use crate::{check_error, check_event};
+use fuels::prelude::{Account, CallParameters, TxPolicies, WalletUnlocked};
 use fuels::types::{Address, AssetId};
 use guild_pin_contract::contract::{GuildAction, GuildPinContract, PinBurned, PinMinted};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::parameters::ParametersBuilder;
-use guild_pin_contract::utils::ClaimBuilder;
+use guild_pin_contract::utils::{pin_sub_id, ClaimBuilder};
 
 #[tokio::test]
 async fn burn_successful() {
@@ -10,7 +12,8 @@ async fn burn_successful() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     let response = contract
@@ -55,8 +58,31 @@ async fn burn_successful() {
         .unwrap();
     assert_eq!(pin_id, None);
 
-    // claim again
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    assert!(contract
+        .token_of_owner_by_index(alice, 0)
+        .await
+        .unwrap()
+        .is_none());
+    let pin_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.alice.address(), contract.asset_id(0))
+        .await
+        .unwrap();
+    assert_eq!(pin_balance, 0);
+    assert_eq!(contract.asset_supply(0).await.unwrap(), Some(0));
+    assert_eq!(contract.metadata(0).await.unwrap(), None);
+
+    // the same signed claim cannot be replayed
+    let error = contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::MessageAlreadyConsumed);
+
+    // claim again with a new signature
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .signed_at(clp.signed_at - 1)
+        .build();
 
     let signature = parameters.sign_claim(&clp);
     let response = contract
@@ -93,12 +119,13 @@ async fn burn_successful() {
 }
 
 #[tokio::test]
-async fn burn_with_not_owner_fails() {
+async fn burn_by_holder_after_transfer() {
     let parameters = ParametersBuilder::new().test().await;
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     contract
@@ -106,8 +133,41 @@ async fn burn_with_not_owner_fails() {
         .await
         .unwrap();
 
-    let error = contract.burn(&parameters.bob, 0).await.unwrap_err();
-    check_error(error, "NotPinOwner");
+    // the recorded owner is not the holder anymore
+    parameters
+        .alice
+        .transfer(
+            parameters.bob.address(),
+            1,
+            contract.asset_id(0),
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let response = contract.burn(&parameters.bob, 0).await.unwrap();
+    check_event(
+        response,
+        PinBurned {
+            pin_owner: alice,
+            pin_id: 0,
+        },
+    );
+
+    // the recorded owner's bookkeeping is cleaned up
+    assert_eq!(contract.pin_owner(0).await.unwrap(), None);
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 0);
+    assert_eq!(
+        contract.token_of_owner_by_index(alice, 0).await.unwrap(),
+        None
+    );
+    assert_eq!(contract.total_supply().await.unwrap(), 0);
+    let pin_id = contract
+        .pin_id_by_address(alice, clp.guild_id, clp.action)
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+    assert_eq!(contract.asset_supply(0).await.unwrap(), Some(0));
 }
 
 #[tokio::test]
@@ -116,7 +176,8 @@ async fn burn_double_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     contract
@@ -124,9 +185,19 @@ async fn burn_double_fails() {
         .await
         .unwrap();
 
+    // another pin, so alice still has a pin token to send along
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(5678)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
     contract.burn(&parameters.alice, 0).await.unwrap();
-    let error = contract.burn(&parameters.alice, 0).await.unwrap_err();
-    check_error(error, "AlreadyBurned");
+    let error = burn_sending(&contract, &parameters.alice, 0, contract.asset_id(1)).await;
+    check_error(error, GuildPinError::AlreadyBurned);
 }
 
 #[tokio::test]
@@ -135,7 +206,8 @@ async fn burn_non_existent_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     contract
@@ -143,8 +215,56 @@ async fn burn_non_existent_fails() {
         .await
         .unwrap();
 
-    let error = contract.burn(&parameters.alice, 99).await.unwrap_err();
-    check_error(error, "PinIdDoesNotExist");
+    let error = burn_sending(&contract, &parameters.alice, 99, contract.asset_id(0)).await;
+    check_error(error, GuildPinError::PinIdDoesNotExist);
+}
+
+#[tokio::test]
+async fn burn_with_wrong_asset_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    for guild_id in [1234, 5678] {
+        let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+            .guild_id(guild_id)
+            .build();
+        let signature = parameters.sign_claim(&clp);
+        contract
+            .claim(&parameters.alice, clp, signature)
+            .await
+            .unwrap();
+    }
+
+    // pins are distinct assets, so another pin's token cannot be used to burn a pin
+    let error = burn_sending(&contract, &parameters.alice, 0, contract.asset_id(1)).await;
+    check_error(error, GuildPinError::InvalidAssetId);
+    assert_eq!(contract.pin_owner(0).await.unwrap(), Some(alice));
+}
+
+/// Calls burn sending an arbitrary pin token along, which the wrapper doesn't allow.
+async fn burn_sending(
+    contract: &GuildPinContract,
+    caller: &WalletUnlocked,
+    pin_id: u64,
+    asset_id: AssetId,
+) -> GuildPinError {
+    contract
+        .inner()
+        .clone()
+        .with_account(caller.clone())
+        .methods()
+        .burn(pin_sub_id(pin_id), 1)
+        .call_params(
+            CallParameters::default()
+                .with_asset_id(asset_id)
+                .with_amount(1),
+        )
+        .unwrap()
+        .call()
+        .await
+        .unwrap_err()
+        .into()
 }
 
 #[tokio::test]
@@ -175,21 +295,20 @@ async fn flow_successful() {
     // everybody joins guild_0 where
     // alice is admin as well
     // bob is owner as well
-    let claim = ClaimBuilder::new(alice, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(alice_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
         .admin_fee(admin_fee)
-        .chain_id(u64::MAX)
         .build();
-    assert_eq!(claim.chain_id, u64::MAX);
+    assert_eq!(claim.chain_id, *parameters.provider().chain_id());
     let signature = parameters.sign_claim(&claim);
     contract
         .claim(&parameters.alice, claim, signature)
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(bob, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
         .user_id(bob_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
@@ -201,7 +320,7 @@ async fn flow_successful() {
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(charlie, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(charlie, contract.contract_id(), parameters.provider())
         .user_id(charlie_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
@@ -213,7 +332,7 @@ async fn flow_successful() {
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(alice, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(alice_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
@@ -226,7 +345,7 @@ async fn flow_successful() {
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(bob, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
         .user_id(bob_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
@@ -240,7 +359,7 @@ async fn flow_successful() {
         .unwrap();
 
     // alice and bob join guild_1
-    let claim = ClaimBuilder::new(alice, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(alice_id)
         .guild_id(guild_1)
         .admin_treasury(admin_treasury)
@@ -252,7 +371,7 @@ async fn flow_successful() {
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(bob, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
         .user_id(bob_id)
         .guild_id(guild_1)
         .admin_treasury(admin_treasury)
@@ -265,7 +384,7 @@ async fn flow_successful() {
         .unwrap();
 
     // charlie joins guild_2 that doesn't have an admin treasury
-    let claim = ClaimBuilder::new(charlie, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(charlie, contract.contract_id(), parameters.provider())
         .user_id(charlie_id)
         .guild_id(guild_2)
         .build();
@@ -344,12 +463,48 @@ async fn flow_successful() {
     assert_eq!(pin_id, None);
     assert!(contract.pin_owner(0).await.unwrap().is_none());
     assert!(contract.pin_owner(1).await.unwrap().is_none());
+    let pin_id = contract
+        .pin_id_by_user_id(alice_id, guild_0, GuildAction::Joined)
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+    // the owners' last pins fill the burned pins' slots
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 2);
+    assert_eq!(
+        contract.token_of_owner_by_index(alice, 0).await.unwrap(),
+        Some(5)
+    );
+    assert_eq!(
+        contract.token_of_owner_by_index(alice, 1).await.unwrap(),
+        Some(3)
+    );
+    assert!(contract
+        .token_of_owner_by_index(alice, 2)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(contract.balance_of(bob).await.unwrap(), 2);
+    assert_eq!(
+        contract.token_of_owner_by_index(bob, 0).await.unwrap(),
+        Some(6)
+    );
+    assert_eq!(
+        contract.token_of_owner_by_index(bob, 1).await.unwrap(),
+        Some(4)
+    );
+    assert!(contract
+        .token_of_owner_by_index(bob, 2)
+        .await
+        .unwrap()
+        .is_none());
+
     // alice becomes owner of guild_0, so she reclaims the joined pin and the owner pin as well
-    let claim = ClaimBuilder::new(alice, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(alice_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
         .admin_fee(admin_fee)
+        .signed_at(parameters.timestamp().await - 60)
         .build();
     let signature = parameters.sign_claim(&claim);
     contract
@@ -357,7 +512,7 @@ async fn flow_successful() {
         .await
         .unwrap();
 
-    let claim = ClaimBuilder::new(alice, contract.contract_id())
+    let claim = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(alice_id)
         .guild_id(guild_0)
         .admin_treasury(admin_treasury)
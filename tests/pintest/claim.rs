@@ -1,6 +1,8 @@
 use crate::{check_error, check_event};
-use fuels::types::{Address, AssetId, ContractId};
+use fuels::types::{Address, AssetId, ContractId, Identity, Salt};
 use guild_pin_contract::contract::{GuildAction, GuildPinContract, PinMinted};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::events::{decode_events, GuildPinEvent};
 use guild_pin_contract::parameters::ParametersBuilder;
 use guild_pin_contract::utils::ClaimBuilder;
 
@@ -16,7 +18,8 @@ async fn claim_successful() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let mut clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let mut clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     let response = contract
@@ -84,7 +87,7 @@ async fn claim_successful() {
 
     let pin_balance = parameters
         .provider()
-        .get_asset_balance(parameters.alice.address(), contract.asset_id())
+        .get_asset_balance(parameters.alice.address(), contract.asset_id(0))
         .await
         .unwrap();
     assert_eq!(pin_balance, 1);
@@ -94,7 +97,17 @@ async fn claim_successful() {
         .get_contract_balances(contract.bech_contract_id())
         .await
         .unwrap();
-    assert_eq!(pin_balances.get(&contract.asset_id()), Some(&0));
+    assert_eq!(pin_balances.get(&contract.asset_id(0)), Some(&0));
+
+    // every pin is a distinct src20 asset
+    assert_eq!(contract.total_assets().await.unwrap(), 1);
+    assert_eq!(contract.asset_supply(0).await.unwrap(), Some(1));
+    assert_eq!(contract.decimals(0).await.unwrap(), Some(0));
+    assert_eq!(contract.name(0).await.unwrap().unwrap(), "Guild Pin");
+    assert_eq!(contract.symbol(0).await.unwrap().unwrap(), "GUILD");
+    let metadata = contract.metadata(0).await.unwrap();
+    assert_eq!(metadata, Some(contract.pin_metadata(0).await.unwrap()));
+    assert_eq!(contract.asset_supply(1).await.unwrap(), None);
 
     // mint another pin with a different action
     // and admin treasury
@@ -166,26 +179,36 @@ async fn claim_successful() {
         .unwrap();
     assert_eq!(base_balance, genesis_balance + 2 * fee);
 
-    // let base_balance = parameters
-    //     .provider()
-    //     .get_asset_balance(parameters.charlie.address(), AssetId::BASE)
-    //     .await
-    //     .unwrap();
-    // assert_eq!(base_balance, genesis_balance + clp.admin_fee);
-
-    let pin_balance = parameters
+    let base_balance = parameters
         .provider()
-        .get_asset_balance(parameters.alice.address(), contract.asset_id())
+        .get_asset_balance(parameters.charlie.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(base_balance, genesis_balance + clp.admin_fee);
+
+    let admin_fees = contract
+        .admin_fees_per_guild(clp.guild_id, AssetId::BASE)
         .await
         .unwrap();
-    assert_eq!(pin_balance, 2);
+    assert_eq!(admin_fees, clp.admin_fee);
+
+    for pin_id in 0..2 {
+        let pin_balance = parameters
+            .provider()
+            .get_asset_balance(parameters.alice.address(), contract.asset_id(pin_id))
+            .await
+            .unwrap();
+        assert_eq!(pin_balance, 1);
+    }
+    assert_ne!(contract.asset_id(0), contract.asset_id(1));
+    assert_eq!(contract.total_assets().await.unwrap(), 2);
 
     let pin_balances = parameters
         .provider()
         .get_contract_balances(contract.bech_contract_id())
         .await
         .unwrap();
-    assert_eq!(pin_balances.get(&contract.asset_id()), Some(&0));
+    assert_eq!(pin_balances.get(&contract.asset_id(1)), Some(&0));
 }
 
 #[tokio::test]
@@ -202,7 +225,7 @@ async fn token_of_owner_by_index_ok() {
 
     let alice: Address = parameters.alice.address().into();
     let bob: Address = parameters.bob.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id())
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .guild_id(111)
         .build();
 
@@ -212,7 +235,7 @@ async fn token_of_owner_by_index_ok() {
         .await
         .unwrap();
 
-    let clp = ClaimBuilder::new(bob, contract.contract_id())
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
         .guild_id(555)
         .build();
 
@@ -222,7 +245,7 @@ async fn token_of_owner_by_index_ok() {
         .await
         .unwrap();
 
-    let clp = ClaimBuilder::new(alice, contract.contract_id())
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .guild_id(999)
         .build();
 
@@ -263,13 +286,108 @@ async fn token_of_owner_by_index_ok() {
         .is_none());
 }
 
+#[tokio::test]
+async fn admin_fee_forwarding_ok() {
+    let fee = 20;
+    let admin_fee = 15;
+    let genesis_balance = 100;
+    let mut parameters = ParametersBuilder::new()
+        .fee(fee)
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+    // another contract acting as an admin treasury
+    parameters.salt = Salt::from([1u8; 32]);
+    let admin_contract = GuildPinContract::deploy(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let bob: Address = parameters.bob.address().into();
+
+    // admin treasury is a contract
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(111)
+        .admin_treasury(Identity::ContractId(admin_contract.contract_id()))
+        .admin_fee(admin_fee)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let admin_balances = parameters
+        .provider()
+        .get_contract_balances(admin_contract.bech_contract_id())
+        .await
+        .unwrap();
+    assert_eq!(admin_balances.get(&AssetId::BASE), Some(&admin_fee));
+
+    // admin treasury is an address, same guild
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .guild_id(111)
+        .user_id(200)
+        .admin_treasury(parameters.charlie_id())
+        .admin_fee(admin_fee)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.bob, clp, signature)
+        .await
+        .unwrap();
+
+    let charlie_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.charlie.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(charlie_balance, genesis_balance + admin_fee);
+
+    // admin treasury is the pin contract itself, the fee stays in the contract
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(555)
+        .admin_fee(admin_fee)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let pin_balances = parameters
+        .provider()
+        .get_contract_balances(contract.bech_contract_id())
+        .await
+        .unwrap();
+    assert_eq!(pin_balances.get(&AssetId::BASE), Some(&admin_fee));
+
+    let treasury_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.treasury.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(treasury_balance, genesis_balance + 3 * fee);
+
+    let admin_fees = contract
+        .admin_fees_per_guild(111, AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(admin_fees, 2 * admin_fee);
+    let admin_fees = contract
+        .admin_fees_per_guild(555, AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(admin_fees, 0);
+}
+
 #[tokio::test]
 async fn claim_uninitialized_fails() {
     let parameters = ParametersBuilder::new().test().await;
     let contract = GuildPinContract::deploy(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     let error = contract
@@ -277,7 +395,7 @@ async fn claim_uninitialized_fails() {
         .await
         .unwrap_err();
 
-    check_error(error, "NotInitialized");
+    check_error(error, GuildPinError::NotInitialized);
 }
 
 #[tokio::test]
@@ -286,15 +404,26 @@ async fn claim_double_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let mut clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let mut clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
+    assert!(!contract.claim_consumed(&clp).await.unwrap());
     contract
         .claim(&parameters.alice, clp.clone(), signature)
         .await
         .unwrap();
 
     assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    assert!(contract.claim_consumed(&clp).await.unwrap());
+
+    // replay the same signed claim
+    let error = contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap_err();
+
+    check_error(error, GuildPinError::MessageAlreadyConsumed);
 
     // change address
     clp.recipient = parameters.bob.address().into();
@@ -304,10 +433,10 @@ async fn claim_double_fails() {
         .await
         .unwrap_err();
 
-    check_error(error, "AlreadyClaimed");
+    check_error(error, GuildPinError::AlreadyClaimed);
 
     // change user_id
-    let clp = ClaimBuilder::new(alice, contract.contract_id())
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .user_id(99999)
         .build();
     let signature = parameters.sign_claim(&clp);
@@ -316,7 +445,7 @@ async fn claim_double_fails() {
         .await
         .unwrap_err();
 
-    check_error(error, "AlreadyClaimed");
+    check_error(error, GuildPinError::AlreadyClaimed);
 }
 
 #[tokio::test]
@@ -325,14 +454,68 @@ async fn claim_with_invalid_signature_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
     let signature = parameters.sign_alt_claim(&clp);
     let error = contract
         .claim(&parameters.alice, clp, signature)
         .await
         .unwrap_err();
 
-    check_error(error, "InvalidSignature");
+    check_error(error, GuildPinError::InvalidSignature);
+}
+
+#[tokio::test]
+async fn claim_overpayment_refunded() {
+    let fee = 20;
+    let overpayment = 7;
+    let genesis_balance = 100;
+    let parameters = ParametersBuilder::new()
+        .fee(fee)
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    let response = contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+    assert_eq!(response.value, 0);
+
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(555)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let response = contract
+        .unsafe_claim(
+            &parameters.alice,
+            clp,
+            signature,
+            fee + overpayment,
+            AssetId::BASE,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.value, overpayment);
+
+    let base_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.alice.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(base_balance, genesis_balance - 2 * fee);
+
+    let pin_balances = parameters
+        .provider()
+        .get_contract_balances(contract.bech_contract_id())
+        .await
+        .unwrap();
+    assert_eq!(pin_balances.get(&AssetId::BASE), None);
 }
 
 #[tokio::test]
@@ -341,21 +524,22 @@ async fn claim_with_invalid_fee_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
     let signature = parameters.sign_claim(&clp);
     let error = contract
         .unsafe_claim(&parameters.alice, clp.clone(), signature, 0, AssetId::BASE)
         .await
         .unwrap_err();
 
-    check_error(error, "InsufficientAmount");
+    check_error(error, GuildPinError::InsufficientAmount);
 
     let error = contract
-        .unsafe_claim(&parameters.alice, clp, signature, 0, contract.asset_id())
+        .unsafe_claim(&parameters.alice, clp, signature, 0, contract.asset_id(0))
         .await
         .unwrap_err();
 
-    check_error(error, "InvalidAssetId");
+    check_error(error, GuildPinError::AssetNotAccepted);
 }
 
 #[tokio::test]
@@ -364,7 +548,7 @@ async fn claim_with_expired_signature_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id())
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
         .signed_at(parameters.timestamp().await - 4000)
         .build();
     let signature = parameters.sign_claim(&clp);
@@ -373,7 +557,7 @@ async fn claim_with_expired_signature_fails() {
         .await
         .unwrap_err();
 
-    check_error(error, "ExpiredSignature");
+    check_error(error, GuildPinError::ExpiredSignature);
 }
 
 #[tokio::test]
@@ -382,12 +566,285 @@ async fn claim_with_invalid_contract_id_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, ContractId::zeroed()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, ContractId::zeroed(), parameters.provider()).build();
     let signature = parameters.sign_claim(&clp);
     let error = contract
         .claim(&parameters.alice, clp.clone(), signature)
         .await
         .unwrap_err();
 
-    check_error(error, "InvalidContractId");
+    check_error(error, GuildPinError::InvalidContractId);
+}
+
+#[tokio::test]
+async fn claim_with_invalid_chain_id_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+    let provider = parameters.provider();
+
+    let alice: Address = parameters.alice.address().into();
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), provider)
+        .chain_id(*provider.chain_id() + 1)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let error = contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap_err();
+
+    check_error(error, GuildPinError::InvalidChainId);
+
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(matches!(
+        preflight.errors()[..],
+        [GuildPinError::InvalidChainId]
+    ));
+
+    // signed for the connected network
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), provider).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn claim_batch_ok() {
+    let fee = 20;
+    let genesis_balance = 1000;
+    let parameters = ParametersBuilder::new()
+        .fee(fee)
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let bob: Address = parameters.bob.address().into();
+    let charlie: Address = parameters.charlie.address().into();
+
+    let clp_alice =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+            .user_id(1)
+            .build();
+    let clp_bob = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .user_id(2)
+        .build();
+    let clp_charlie =
+        ClaimBuilder::for_provider(charlie, contract.contract_id(), parameters.provider())
+            .user_id(3)
+            .build();
+    let claims = vec![
+        (clp_alice.clone(), parameters.sign_claim(&clp_alice)),
+        (clp_bob.clone(), parameters.sign_alt_claim(&clp_bob)),
+        (clp_charlie.clone(), parameters.sign_claim(&clp_charlie)),
+    ];
+
+    // the relayer pays every fee
+    let response = contract
+        .claim_batch(&parameters.owner, claims)
+        .await
+        .unwrap();
+    assert!(response.tx_id.is_some());
+    // bob's claim was signed by the wrong signer
+    assert_eq!(response.failed.len(), 1);
+    assert_eq!(response.failed[0].0, 1);
+
+    let events = decode_events(&contract.inner().log_decoder(), &response.receipts).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            GuildPinEvent::PinMinted(PinMinted {
+                recipient: alice,
+                pin_id: 0,
+            }),
+            GuildPinEvent::PinMinted(PinMinted {
+                recipient: charlie,
+                pin_id: 1,
+            }),
+        ]
+    );
+
+    assert_eq!(contract.total_minted().await.unwrap(), 2);
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    assert_eq!(contract.balance_of(bob).await.unwrap(), 0);
+    assert_eq!(contract.balance_of(charlie).await.unwrap(), 1);
+
+    let base_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.treasury.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(base_balance, genesis_balance + 2 * fee);
+
+    // nothing is submitted if every claim fails
+    let claims = vec![(clp_alice.clone(), parameters.sign_claim(&clp_alice))];
+    let response = contract
+        .claim_batch(&parameters.owner, claims)
+        .await
+        .unwrap();
+    assert!(response.tx_id.is_none());
+    assert!(response.receipts.is_empty());
+    assert_eq!(response.failed.len(), 1);
+}
+
+#[tokio::test]
+async fn claim_batch_records_fee_failures() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    // only the guild with a negotiated fee can still be paid for in the base asset
+    let guild_id = 5678;
+    contract
+        .set_fee_override(&parameters.owner, Some(guild_id), None, Some(5))
+        .await
+        .unwrap();
+    contract
+        .remove_payment_asset(&parameters.owner, AssetId::BASE)
+        .await
+        .unwrap();
+
+    let alice: Address = parameters.alice.address().into();
+    let bob: Address = parameters.bob.address().into();
+    let clp_alice =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+            .user_id(1)
+            .build();
+    let clp_bob = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .user_id(2)
+        .guild_id(guild_id)
+        .build();
+    let claims = vec![
+        (clp_alice.clone(), parameters.sign_claim(&clp_alice)),
+        (clp_bob.clone(), parameters.sign_claim(&clp_bob)),
+    ];
+
+    // alice's fee can't be determined, which doesn't abort bob's claim
+    let response = contract
+        .claim_batch(&parameters.owner, claims)
+        .await
+        .unwrap();
+    assert!(response.tx_id.is_some());
+    assert_eq!(response.failed.len(), 1);
+    let (index, error) = response.failed.into_iter().next().unwrap();
+    assert_eq!(index, 0);
+    check_error(error, GuildPinError::AssetNotAccepted);
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 0);
+    assert_eq!(contract.balance_of(bob).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn preflight_claim_ok() {
+    let fee = 20;
+    let genesis_balance = 100;
+    let parameters = ParametersBuilder::new()
+        .fee(fee)
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::deploy(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(!preflight.initialized);
+    assert!(matches!(
+        preflight.errors()[..],
+        [
+            GuildPinError::NotInitialized,
+            GuildPinError::InvalidSignature
+        ]
+    ));
+
+    contract.initialize(&parameters.owner).await.unwrap();
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(preflight.is_ok());
+    assert_eq!(preflight.required_amount, Some(fee));
+    assert_eq!(preflight.balance, genesis_balance);
+
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(preflight.consumed);
+    assert_eq!(preflight.claimed_by_address, Some(0));
+    assert_eq!(preflight.claimed_by_user_id, Some(0));
+    assert!(matches!(
+        preflight.errors()[..],
+        [
+            GuildPinError::MessageAlreadyConsumed,
+            GuildPinError::AlreadyClaimed
+        ]
+    ));
+
+    // expired, signed by the wrong signer and too expensive
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(5678)
+        .admin_fee(genesis_balance)
+        .signed_at(parameters.timestamp().await - 4000)
+        .build();
+    let signature = parameters.sign_alt_claim(&clp);
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(matches!(
+        preflight.errors()[..],
+        [
+            GuildPinError::ExpiredSignature,
+            GuildPinError::InvalidSignature,
+            GuildPinError::InsufficientAmount
+        ]
+    ));
+
+    // wrong contract id
+    let clp = ClaimBuilder::for_provider(alice, ContractId::zeroed(), parameters.provider())
+        .guild_id(5678)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert!(matches!(
+        preflight.errors()[..],
+        [GuildPinError::InvalidContractId]
+    ));
+
+    // the base asset is no longer accepted
+    contract
+        .remove_payment_asset(&parameters.owner, AssetId::BASE)
+        .await
+        .unwrap();
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(5678)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let preflight = contract
+        .preflight_claim(&parameters.alice, &clp, signature)
+        .await
+        .unwrap();
+    assert_eq!(preflight.required_amount, None);
+    assert!(matches!(
+        preflight.errors()[..],
+        [GuildPinError::AssetNotAccepted]
+    ));
 }
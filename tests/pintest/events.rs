@@ -0,0 +1,80 @@
+use fuels::prelude::MultiContractCallHandler;
+use fuels::types::Address;
+use guild_pin_contract::contract::{
+    ContractInitialized, FeeChanged, GuildPinContract, OwnershipSet, PinMinted, TreasuryChanged,
+};
+use guild_pin_contract::events::GuildPinEvent;
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn decode_all_events() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::deploy(&parameters).await;
+
+    let response = contract.initialize(&parameters.owner).await.unwrap();
+    assert_eq!(
+        contract.events(&response).unwrap(),
+        vec![
+            GuildPinEvent::OwnershipSet(OwnershipSet {
+                new_owner: parameters.owner_id(),
+            }),
+            GuildPinEvent::ContractInitialized(ContractInitialized {
+                owner: parameters.owner_id(),
+                signer: parameters.signer_evm(),
+                treasury: parameters.treasury_id(),
+                fee: parameters.fee,
+            }),
+        ]
+    );
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    let response = contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+    assert_eq!(
+        contract.events(&response).unwrap(),
+        vec![GuildPinEvent::PinMinted(PinMinted {
+            recipient: alice,
+            pin_id: 0,
+        })]
+    );
+}
+
+#[tokio::test]
+async fn decode_multicall_events() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    // an owner transaction changing multiple settings at once
+    let owner_contract = contract
+        .inner()
+        .clone()
+        .with_account(parameters.owner.clone());
+    let set_fee = owner_contract.methods().set_fee(100);
+    let set_treasury = owner_contract.methods().set_treasury(parameters.bob_id());
+    let response = MultiContractCallHandler::new(parameters.owner.clone())
+        .add_call(set_treasury)
+        .add_call(set_fee)
+        .call::<((), ())>()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contract.events(&response).unwrap(),
+        vec![
+            GuildPinEvent::TreasuryChanged(TreasuryChanged {
+                old: parameters.treasury_id(),
+                new: parameters.bob_id(),
+            }),
+            GuildPinEvent::FeeChanged(FeeChanged {
+                old: parameters.fee,
+                new: 100,
+            }),
+        ]
+    );
+}
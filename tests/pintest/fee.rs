@@ -1,6 +1,9 @@
 use crate::{check_error, check_event};
-use guild_pin_contract::contract::{FeeChanged, GuildPinContract};
-use guild_pin_contract::parameters::ParametersBuilder;
+use fuels::types::{Address, AssetId};
+use guild_pin_contract::contract::{FeeChanged, FeeOverrideChanged, GuildAction, GuildPinContract};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::parameters::{ParametersBuilder, TEST_ASSET_ID};
+use guild_pin_contract::utils::ClaimBuilder;
 
 #[tokio::test]
 async fn set_fee_success() {
@@ -30,8 +33,263 @@ async fn set_fee_fails() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let error = contract.set_fee(&parameters.bob, 0).await.unwrap_err();
-    check_error(error, "NotOwner");
+    check_error(error, GuildPinError::NotOwner);
 
     let contract_fee = contract.fee().await.unwrap();
     assert_eq!(contract_fee, parameters.fee);
 }
+
+#[tokio::test]
+async fn fee_overrides_success() {
+    let parameters = ParametersBuilder::new().fee(10).test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let guild_id = 1234;
+    let role = GuildAction::Role(42);
+    assert_eq!(contract.fee_for(guild_id, role.clone()).await.unwrap(), 10);
+
+    let response = contract
+        .set_fee_override(&parameters.owner, None, Some(role.clone()), Some(7))
+        .await
+        .unwrap();
+    check_event(
+        response,
+        FeeOverrideChanged {
+            guild_id: None,
+            action: Some(role.clone()),
+            old: None,
+            new: Some(7),
+        },
+    );
+    assert_eq!(contract.fee_for(guild_id, role.clone()).await.unwrap(), 7);
+    assert_eq!(contract.fee_for(11, role.clone()).await.unwrap(), 7);
+    // the override applies to every role, not just the given one
+    assert_eq!(
+        contract
+            .fee_for(guild_id, GuildAction::Role(43))
+            .await
+            .unwrap(),
+        7
+    );
+    assert_eq!(
+        contract
+            .fee_override(None, Some(GuildAction::Role(43)))
+            .await
+            .unwrap(),
+        Some(7)
+    );
+    // but other actions are not affected
+    assert_eq!(
+        contract
+            .fee_for(guild_id, GuildAction::Admin)
+            .await
+            .unwrap(),
+        10
+    );
+
+    // the guild's fee takes precedence over that of the action
+    contract
+        .set_fee_override(&parameters.owner, Some(guild_id), None, Some(5))
+        .await
+        .unwrap();
+    assert_eq!(contract.fee_for(guild_id, role.clone()).await.unwrap(), 5);
+    assert_eq!(
+        contract
+            .fee_for(guild_id, GuildAction::Joined)
+            .await
+            .unwrap(),
+        5
+    );
+    assert_eq!(contract.fee_for(11, role.clone()).await.unwrap(), 7);
+
+    // and that of the action in the guild takes precedence over both
+    contract
+        .set_fee_override(
+            &parameters.owner,
+            Some(guild_id),
+            Some(role.clone()),
+            Some(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(contract.fee_for(guild_id, role.clone()).await.unwrap(), 0);
+    assert_eq!(
+        contract
+            .fee_override(Some(guild_id), Some(role.clone()))
+            .await
+            .unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        contract.fee_override(Some(guild_id), None).await.unwrap(),
+        Some(5)
+    );
+
+    // overrides can be updated and removed
+    let response = contract
+        .set_fee_override(&parameters.owner, Some(guild_id), None, Some(3))
+        .await
+        .unwrap();
+    check_event(
+        response,
+        FeeOverrideChanged {
+            guild_id: Some(guild_id),
+            action: None,
+            old: Some(5),
+            new: Some(3),
+        },
+    );
+    let response = contract
+        .set_fee_override(&parameters.owner, Some(guild_id), Some(role.clone()), None)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        FeeOverrideChanged {
+            guild_id: Some(guild_id),
+            action: Some(role.clone()),
+            old: Some(0),
+            new: None,
+        },
+    );
+    assert_eq!(contract.fee_for(guild_id, role.clone()).await.unwrap(), 3);
+
+    // the global fee still applies to everyone else
+    assert_eq!(contract.fee_for(11, GuildAction::Owner).await.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn fee_overrides_fail() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let error = contract
+        .set_fee_override(&parameters.bob, Some(1234), None, Some(0))
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    let error = contract
+        .set_fee_override(&parameters.owner, None, None, Some(0))
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::MissingFeeScope);
+
+    assert_eq!(
+        contract.fee_for(1234, GuildAction::Joined).await.unwrap(),
+        parameters.fee
+    );
+}
+
+#[tokio::test]
+async fn claim_with_fee_override() {
+    let genesis_balance = 100;
+    let parameters = ParametersBuilder::new()
+        .fee(20)
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    contract
+        .set_fee_override(&parameters.owner, Some(clp.guild_id), None, Some(5))
+        .await
+        .unwrap();
+
+    // the claim attaches the guild's fee
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let provider = parameters.provider();
+    let balance = provider
+        .get_asset_balance(parameters.alice.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(balance, genesis_balance - 5);
+    let balance = provider
+        .get_asset_balance(parameters.treasury.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(balance, genesis_balance + 5);
+
+    // paying the global fee is refunded down to the guild's fee
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Owner)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .unsafe_claim(&parameters.alice, clp, signature, 20, AssetId::BASE)
+        .await
+        .unwrap();
+    let balance = provider
+        .get_asset_balance(parameters.alice.address(), AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(balance, genesis_balance - 10);
+}
+
+#[tokio::test]
+async fn fee_override_requires_base_asset() {
+    let asset_fee = 3;
+    let parameters = ParametersBuilder::new().fee(20).test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+    contract
+        .set_payment_asset(&parameters.owner, TEST_ASSET_ID, asset_fee)
+        .await
+        .unwrap();
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    contract
+        .set_fee_override(&parameters.owner, Some(clp.guild_id), None, Some(5))
+        .await
+        .unwrap();
+
+    // the negotiated fee is priced in the base asset, so other assets are rejected
+    let signature = parameters.sign_claim(&clp);
+    let error = contract
+        .claim_with_asset(&parameters.alice, clp.clone(), signature, TEST_ASSET_ID)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::BaseAssetRequired);
+
+    // while claims without an override may still be paid in any accepted asset
+    let other = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .guild_id(5678)
+        .build();
+    let signature = parameters.sign_claim(&other);
+    contract
+        .claim_with_asset(&parameters.alice, other, signature, TEST_ASSET_ID)
+        .await
+        .unwrap();
+
+    // the override still applies once the base asset is no longer accepted in general
+    contract
+        .remove_payment_asset(&parameters.owner, AssetId::BASE)
+        .await
+        .unwrap();
+    assert_eq!(
+        contract
+            .fee_for(clp.guild_id, clp.action.clone())
+            .await
+            .unwrap(),
+        5
+    );
+    let error = contract
+        .fee_for(5678, GuildAction::Joined)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+}
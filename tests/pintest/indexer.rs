@@ -0,0 +1,98 @@
+use fuels::types::Address;
+use guild_pin_contract::contract::GuildPinContract;
+use guild_pin_contract::indexer::{Event, Indexer, JsonLinesStore};
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn index_and_resume() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let bob: Address = parameters.bob.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+    contract.set_fee(&parameters.owner, 30).await.unwrap();
+
+    let dir = std::env::temp_dir().join(format!("guild-pin-indexer-{}", contract.contract_id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let events_path = dir.join("pins.jsonl");
+    let _ = std::fs::remove_file(&events_path);
+    let _ = std::fs::remove_file(events_path.with_extension("checkpoint"));
+
+    let store = JsonLinesStore::open(&events_path).unwrap();
+    let indexer = Indexer::new(&contract, parameters.provider().clone(), store, 0);
+    let next_height = indexer.sync().await.unwrap();
+    assert_eq!(indexer.store().checkpoint().unwrap(), Some(next_height));
+
+    let events = indexer
+        .store()
+        .records()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.event)
+        .collect::<Vec<Event>>();
+    assert_eq!(
+        events[0],
+        Event::OwnershipSet {
+            new_owner: parameters.owner_id(),
+        }
+    );
+    assert_eq!(
+        events[1],
+        Event::ContractInitialized {
+            owner: parameters.owner_id(),
+            signer: parameters.signer_evm(),
+            treasury: parameters.treasury_id(),
+            fee: parameters.fee,
+        }
+    );
+    assert!(events.contains(&Event::PinMinted {
+        recipient: alice,
+        pin_id: 0,
+    }));
+    assert_eq!(
+        events.last(),
+        Some(&Event::FeeChanged {
+            old: parameters.fee,
+            new: 30,
+        })
+    );
+
+    // restart the indexer after another claim
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .user_id(200)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.bob, clp, signature)
+        .await
+        .unwrap();
+
+    let store = JsonLinesStore::open(&events_path).unwrap();
+    let indexer = Indexer::new(&contract, parameters.provider().clone(), store, 0);
+    assert_eq!(indexer.store().records().unwrap().len(), events.len());
+    indexer.sync().await.unwrap();
+
+    let records = indexer.store().records().unwrap();
+    assert_eq!(records.len(), events.len() + 1);
+    assert!(records[events.len()].block_height >= next_height);
+    assert_eq!(indexer.store().pins_of(alice).unwrap(), vec![0]);
+    assert_eq!(indexer.store().pins_of(bob).unwrap(), vec![1]);
+
+    // burned pins are no longer owned
+    contract.burn(&parameters.alice, 0).await.unwrap();
+    indexer.sync().await.unwrap();
+    assert!(indexer.store().pins_of(alice).unwrap().is_empty());
+    assert!(!indexer
+        .store()
+        .pins_by_owner()
+        .unwrap()
+        .contains_key(&alice));
+}
@@ -1,5 +1,7 @@
 use crate::{check_error, check_event};
+use fuels::types::AssetId;
 use guild_pin_contract::contract::{ContractInitialized, GuildPinContract};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::parameters::ParametersBuilder;
 
 #[tokio::test]
@@ -18,12 +20,19 @@ async fn init_by_owner_success() {
         },
     );
 
-    // sanity src20 tests
-    assert_eq!(contract.total_assets().await.unwrap(), 1);
-    assert_eq!(contract.decimals().await.unwrap(), 0);
-    assert_eq!(contract.name().await.unwrap(), "Guild Pin");
-    assert_eq!(contract.symbol().await.unwrap(), "GUILD");
+    // sanity src20 tests, there are no pin assets yet
+    assert_eq!(contract.total_assets().await.unwrap(), 0);
+    assert_eq!(contract.decimals(0).await.unwrap(), None);
+    assert_eq!(contract.name(0).await.unwrap(), None);
+    assert_eq!(contract.symbol(0).await.unwrap(), None);
+    assert_eq!(contract.asset_supply(0).await.unwrap(), None);
     assert_eq!(contract.total_supply().await.unwrap(), 0);
+
+    // the base asset is accepted with the initial fee
+    let payment_assets = contract.payment_assets().await.unwrap();
+    assert_eq!(payment_assets, vec![AssetId::BASE]);
+    let payment_fee = contract.payment_fee(AssetId::BASE).await.unwrap();
+    assert_eq!(payment_fee, Some(parameters.fee));
 }
 
 #[tokio::test]
@@ -47,5 +56,5 @@ async fn init_by_random_success() {
 
     // cannot initialize twice
     let error = contract.initialize(&parameters.owner).await.unwrap_err();
-    check_error(error, "AlreadyInitialized");
+    check_error(error, GuildPinError::AlreadyInitialized);
 }
@@ -1,30 +1,43 @@
 #![warn(clippy::all)]
 #![warn(clippy::dbg_macro)]
 
-// NOTE burn is disabled
-//pub mod burn;
+pub mod burn;
 pub mod claim;
+pub mod events;
 pub mod fee;
+pub mod indexer;
 pub mod init;
 pub mod metadata;
 pub mod owner;
+pub mod payment;
+pub mod reconcile;
+pub mod revoke;
+pub mod role;
 pub mod signer;
+pub mod soulbound;
+pub mod src3;
 pub mod treasury;
+pub mod update;
+pub mod withdraw;
 
 use fuels::core::traits::{Parameterize, Tokenizable};
 use fuels::programs::call_response::FuelCallResponse;
-use fuels::types::errors::transaction::Reason;
-use fuels::types::errors::Error;
+use guild_pin_contract::error::GuildPinError;
 
 use std::fmt::Debug;
 
-fn check_error(error: Error, expected: &str) {
-    match error {
-        Error::Transaction(Reason::Reverted { reason, .. }) => {
-            assert_eq!(reason, expected);
-        }
-        _ => panic!("invalid error type"),
-    }
+// NOTE sdk errors never match, not even each other, so that an unexpected transport failure or an
+// unrecognized revert can't pass for an expected error
+fn check_error(error: GuildPinError, expected: GuildPinError) {
+    let is_sdk_error = |error: &GuildPinError| matches!(error, GuildPinError::Fuels(_));
+    assert!(
+        !is_sdk_error(&error)
+            && !is_sdk_error(&expected)
+            && std::mem::discriminant(&error) == std::mem::discriminant(&expected),
+        "expected {:?}, got {:?}",
+        expected,
+        error
+    );
 }
 
 fn check_event<R, T>(response: FuelCallResponse<R>, expected: T)
@@ -1,10 +1,13 @@
 use crate::{check_error, check_event};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use fuels::types::Address;
-use guild_pin_contract::contract::{GuildPinContract, PinMinted};
+use fuels::types::{Address, SizedAsciiString};
+use guild_pin_contract::contract::{GuildAction, GuildPinContract, PinMinted};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::metadata::*;
 use guild_pin_contract::parameters::ParametersBuilder;
 use guild_pin_contract::utils::ClaimBuilder;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
 
 #[tokio::test]
 async fn metadata_ok() {
@@ -12,7 +15,8 @@ async fn metadata_ok() {
     let contract = GuildPinContract::init(&parameters).await;
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     let response = contract
@@ -81,6 +85,164 @@ async fn metadata_ok() {
     let json_value: serde_json::Value = serde_json::from_str(&metadata).unwrap();
     assert_eq!(json_value["attributes"][4]["display_type"], "date");
     assert_eq!(json_value["attributes"][5]["display_type"], "date");
+
+    // offline encoding yields the exact same bytes
+    let pin_data = PinData::from_claim(&clp, mint_date);
+    assert_eq!(pin_data.encode(0), metadata);
+    assert_eq!(
+        pin_data.encoded_metadata(0),
+        contract.encoded_metadata(0).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn offline_metadata_matches_onchain() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let mut rng = rand::thread_rng();
+    for pin_id in 0..16 {
+        let action = match rng.gen_range(0..4) {
+            0 => GuildAction::Joined,
+            1 => GuildAction::Owner,
+            2 => GuildAction::Admin,
+            _ => GuildAction::Role(rng.gen()),
+        };
+        let mut clp =
+            ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+                .action(action)
+                .user_id(rng.gen())
+                .guild_id(rng.gen())
+                .build();
+        clp.created_at = rng.gen_range(0..parameters.timestamp().await);
+        // the right padding of varying length has to be stripped
+        clp.guild_name =
+            SizedAsciiString::new_with_right_whitespace_padding(random_ascii(&mut rng)).unwrap();
+        clp.cid =
+            SizedAsciiString::new_with_right_whitespace_padding(random_ascii(&mut rng)).unwrap();
+
+        let signature = parameters.sign_claim(&clp);
+        contract
+            .claim(&parameters.alice, clp.clone(), signature)
+            .await
+            .unwrap();
+
+        let metadata = contract.pin_metadata(pin_id).await.unwrap();
+        let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+        let mint_date = token_uri
+            .attributes
+            .iter()
+            .find_map(|attr| attr.mint_date())
+            .unwrap();
+
+        let pin_data = PinData::from_claim(&clp, mint_date);
+        assert_eq!(pin_data.encode(pin_id), metadata);
+        assert_eq!(
+            pin_data.encoded_metadata(pin_id),
+            contract.encoded_metadata(pin_id).await.unwrap()
+        );
+    }
+}
+
+/// Returns at most 64 alphanumeric characters and spaces that don't need escaping in json.
+fn random_ascii(rng: &mut impl Rng) -> String {
+    let len = rng.gen_range(0..=64);
+    (0..len)
+        .map(|_| {
+            if rng.gen_ratio(1, 8) {
+                ' '
+            } else {
+                rng.sample(Alphanumeric) as char
+            }
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn src9_metadata_keys_match_json() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let bob: Address = parameters.bob.address().into();
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Admin)
+        .user_id(12)
+        .guild_id(98)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.bob, clp, signature)
+        .await
+        .unwrap();
+
+    let metadata = contract.pin_metadata(0).await.unwrap();
+    assert_eq!(contract.metadata(0).await.unwrap(), Some(metadata.clone()));
+    let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+    let json_value: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+
+    let name = contract.metadata_name(0).await.unwrap().unwrap();
+    assert_eq!(
+        serde_json::from_value::<Action>(name.into()).unwrap(),
+        token_uri.name
+    );
+    assert_eq!(
+        contract.metadata_description(0).await.unwrap(),
+        Some(token_uri.description)
+    );
+    assert_eq!(
+        contract.metadata_image(0).await.unwrap(),
+        Some(token_uri.image)
+    );
+
+    let attributes = [
+        Attribute::Type(
+            serde_json::from_value(contract.metadata_type(0).await.unwrap().unwrap().into())
+                .unwrap(),
+        ),
+        Attribute::GuildId(contract.metadata_guild_id(0).await.unwrap().unwrap()),
+        Attribute::UserId(contract.metadata_user_id(0).await.unwrap().unwrap()),
+        Attribute::Rank(contract.metadata_rank(0).await.unwrap().unwrap()),
+        Attribute::MintDate(
+            contract
+                .metadata_mint_date(0)
+                .await
+                .unwrap()
+                .unwrap()
+                .to_string(),
+        ),
+        Attribute::ActionDate(
+            contract
+                .metadata_action_date(0)
+                .await
+                .unwrap()
+                .unwrap()
+                .to_string(),
+        ),
+    ];
+    for attribute in attributes {
+        assert!(token_uri.attributes.contains(&attribute), "{:?}", attribute);
+    }
+    assert_eq!(
+        contract.metadata_status(0).await.unwrap().as_deref(),
+        Some("active")
+    );
+    assert_eq!(token_uri.attributes[6], Attribute::Status(Status::Active));
+    assert_eq!(json_value["attributes"].as_array().unwrap().len(), 7);
+
+    // unknown keys and burned pins have no metadata
+    let unknown = contract
+        .inner()
+        .methods()
+        .metadata(contract.asset_id(0), "color".to_string())
+        .simulate()
+        .await
+        .unwrap()
+        .value;
+    assert!(unknown.is_none());
+    contract.burn(&parameters.bob, 0).await.unwrap();
+    assert_eq!(contract.metadata_name(0).await.unwrap(), None);
+    assert_eq!(contract.metadata_guild_id(0).await.unwrap(), None);
 }
 
 #[tokio::test]
@@ -98,10 +260,11 @@ async fn metadata_nonexistent_fails() {
         .call()
         .await
         .unwrap_err();
-    check_error(error, "PinIdDoesNotExist");
+    check_error(error.into(), GuildPinError::PinIdDoesNotExist);
 
     let alice: Address = parameters.alice.address().into();
-    let clp = ClaimBuilder::new(alice, contract.contract_id()).build();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
 
     let signature = parameters.sign_claim(&clp);
     contract
@@ -111,8 +274,6 @@ async fn metadata_nonexistent_fails() {
 
     contract.pin_metadata(0).await.unwrap();
 
-    // NOTE burn is disabled
-    /*
     contract.burn(&parameters.alice, 0).await.unwrap();
 
     let error = contract
@@ -122,6 +283,5 @@ async fn metadata_nonexistent_fails() {
         .call()
         .await
         .unwrap_err();
-    check_error(error, "PinIdDoesNotExist");
-    */
+    check_error(error.into(), GuildPinError::PinIdDoesNotExist);
 }
@@ -1,5 +1,6 @@
 use crate::{check_error, check_event};
 use guild_pin_contract::contract::{GuildPinContract, OwnershipTransferred};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::parameters::ParametersBuilder;
 
 #[tokio::test]
@@ -8,7 +9,7 @@ async fn set_owner_success() {
     let contract = GuildPinContract::deploy(&parameters).await;
 
     let error = contract.owner().await.unwrap_err();
-    assert_eq!(error.to_string(), "io: NotInitialized");
+    check_error(error, GuildPinError::NotInitialized);
 
     contract.initialize(&parameters.bob).await.unwrap();
     let owner = contract.owner().await.unwrap();
@@ -39,7 +40,7 @@ async fn set_owner_fails() {
         .set_owner(&parameters.owner, parameters.owner_id())
         .await
         .unwrap_err();
-    check_error(error, "NotOwner");
+    check_error(error, GuildPinError::NotOwner);
 
     contract.initialize(&parameters.owner).await.unwrap();
 
@@ -50,7 +51,7 @@ async fn set_owner_fails() {
         .set_owner(&parameters.charlie, parameters.charlie_id())
         .await
         .unwrap_err();
-    check_error(error, "NotOwner");
+    check_error(error, GuildPinError::NotOwner);
 
     let owner = contract.owner().await.unwrap();
     assert_eq!(owner, parameters.owner_id());
@@ -0,0 +1,189 @@
+use crate::{check_error, check_event};
+use fuels::types::{Address, AssetId};
+use guild_pin_contract::contract::{
+    FeeChanged, GuildPinContract, PaymentAssetRemoved, PaymentAssetSet, PinMinted,
+};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::parameters::{ParametersBuilder, TEST_ASSET_ID};
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn set_payment_asset_success() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let response = contract
+        .set_payment_asset(&parameters.owner, TEST_ASSET_ID, 5)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PaymentAssetSet {
+            asset: TEST_ASSET_ID,
+            old_fee: None,
+            new_fee: 5,
+        },
+    );
+
+    let response = contract
+        .set_payment_asset(&parameters.owner, TEST_ASSET_ID, 7)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PaymentAssetSet {
+            asset: TEST_ASSET_ID,
+            old_fee: Some(5),
+            new_fee: 7,
+        },
+    );
+
+    let payment_assets = contract.payment_assets().await.unwrap();
+    assert_eq!(payment_assets, vec![AssetId::BASE, TEST_ASSET_ID]);
+    let payment_fee = contract.payment_fee(TEST_ASSET_ID).await.unwrap();
+    assert_eq!(payment_fee, Some(7));
+
+    // the base asset fee is kept in sync with `fee`
+    let response = contract.set_fee(&parameters.owner, 30).await.unwrap();
+    check_event(
+        response,
+        FeeChanged {
+            old: parameters.fee,
+            new: 30,
+        },
+    );
+    let payment_fee = contract.payment_fee(AssetId::BASE).await.unwrap();
+    assert_eq!(payment_fee, Some(30));
+
+    let response = contract
+        .remove_payment_asset(&parameters.owner, TEST_ASSET_ID)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PaymentAssetRemoved {
+            asset: TEST_ASSET_ID,
+            fee: 7,
+        },
+    );
+
+    let payment_assets = contract.payment_assets().await.unwrap();
+    assert_eq!(payment_assets, vec![AssetId::BASE]);
+    let payment_fee = contract.payment_fee(TEST_ASSET_ID).await.unwrap();
+    assert_eq!(payment_fee, None);
+}
+
+#[tokio::test]
+async fn set_payment_asset_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let error = contract
+        .set_payment_asset(&parameters.bob, TEST_ASSET_ID, 5)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    let error = contract
+        .remove_payment_asset(&parameters.bob, AssetId::BASE)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    let error = contract
+        .remove_payment_asset(&parameters.owner, TEST_ASSET_ID)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+
+    let payment_assets = contract.payment_assets().await.unwrap();
+    assert_eq!(payment_assets, vec![AssetId::BASE]);
+}
+
+#[tokio::test]
+async fn removed_base_asset_has_no_fee() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    contract
+        .remove_payment_asset(&parameters.owner, AssetId::BASE)
+        .await
+        .unwrap();
+
+    // the fee is not reported as zero, since claims paying with the base asset are rejected
+    let error = contract.fee().await.unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    let error = contract
+        .unsafe_claim(&parameters.alice, clp, signature, 0, AssetId::BASE)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+}
+
+#[tokio::test]
+async fn claim_with_payment_asset() {
+    let asset_fee = 3;
+    let genesis_balance = 100;
+    let parameters = ParametersBuilder::new()
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+    let provider = parameters.provider();
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+
+    // not accepted yet
+    let error = contract
+        .claim_with_asset(&parameters.alice, clp.clone(), signature, TEST_ASSET_ID)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+
+    contract
+        .set_payment_asset(&parameters.owner, TEST_ASSET_ID, asset_fee)
+        .await
+        .unwrap();
+
+    let response = contract
+        .claim_with_asset(&parameters.alice, clp.clone(), signature, TEST_ASSET_ID)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PinMinted {
+            recipient: alice,
+            pin_id: 0,
+        },
+    );
+
+    let treasury_balance = provider
+        .get_asset_balance(parameters.treasury.address(), TEST_ASSET_ID)
+        .await
+        .unwrap();
+    assert_eq!(treasury_balance, genesis_balance + asset_fee);
+
+    // removed assets are rejected by the contract as well
+    contract
+        .remove_payment_asset(&parameters.owner, TEST_ASSET_ID)
+        .await
+        .unwrap();
+    let bob: Address = parameters.bob.address().into();
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .user_id(200)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let error = contract
+        .unsafe_claim(&parameters.bob, clp, signature, asset_fee, TEST_ASSET_ID)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AssetNotAccepted);
+}
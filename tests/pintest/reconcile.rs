@@ -0,0 +1,82 @@
+use fuels::prelude::{Account, TxPolicies};
+use fuels::types::Address;
+use guild_pin_contract::contract::GuildPinContract;
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::reconcile::reconcile;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn reconcile_reports_drift() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let bob: Address = parameters.bob.address().into();
+    let charlie: Address = parameters.charlie.address().into();
+    for (recipient, guild_id) in [(alice, 1234), (alice, 5678)] {
+        let clp =
+            ClaimBuilder::for_provider(recipient, contract.contract_id(), parameters.provider())
+                .guild_id(guild_id)
+                .build();
+        let signature = parameters.sign_claim(&clp);
+        contract
+            .claim(&parameters.alice, clp, signature)
+            .await
+            .unwrap();
+    }
+    let clp = ClaimBuilder::for_provider(bob, contract.contract_id(), parameters.provider())
+        .user_id(200)
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.bob, clp, signature)
+        .await
+        .unwrap();
+
+    let report = reconcile(&contract, parameters.provider(), &[charlie])
+        .await
+        .unwrap();
+    // charlie holds nothing yet
+    assert_eq!(report.holders.len(), 2);
+    assert!(!report.has_drift());
+
+    // alice sends her second pin to charlie, the contract still thinks she owns it
+    parameters
+        .alice
+        .transfer(
+            parameters.charlie.address(),
+            1,
+            contract.asset_id(1),
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let report = reconcile(&contract, parameters.provider(), &[charlie])
+        .await
+        .unwrap();
+    let drifted = report.drifted().collect::<Vec<_>>();
+    assert_eq!(drifted.len(), 2);
+    let alice_drift = drifted.iter().find(|h| h.holder == alice).unwrap();
+    assert_eq!(alice_drift.recorded_balance, 2);
+    assert_eq!(alice_drift.recorded_pins, vec![0, 1]);
+    assert_eq!(alice_drift.missing_pins, vec![1]);
+    assert!(alice_drift.unrecorded_pins.is_empty());
+    assert_eq!(alice_drift.held_balance(), 1);
+    let charlie_drift = drifted.iter().find(|h| h.holder == charlie).unwrap();
+    assert_eq!(charlie_drift.recorded_balance, 0);
+    assert!(charlie_drift.recorded_pins.is_empty());
+    assert_eq!(charlie_drift.unrecorded_pins, vec![1]);
+    assert_eq!(charlie_drift.held_balance(), 1);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["holders"].as_array().unwrap().len(), 3);
+    assert_eq!(report.to_csv().lines().count(), 4);
+
+    // without charlie as a candidate only the missing pin shows up
+    let report = reconcile(&contract, parameters.provider(), &[])
+        .await
+        .unwrap();
+    assert_eq!(report.holders.len(), 2);
+    assert_eq!(report.drifted().count(), 1);
+}
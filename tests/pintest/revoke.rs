@@ -0,0 +1,132 @@
+use crate::{check_error, check_event};
+use fuels::types::Address;
+use guild_pin_contract::contract::{GuildPinContract, PinRevoked, RevocationReason};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::metadata::{Attribute, PinData, Status, TokenUri};
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn revoke_ok() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+
+    let response = contract
+        .revoke(&parameters.owner, 0, RevocationReason::LeftGuild)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PinRevoked {
+            pin_owner: alice,
+            pin_id: 0,
+            reason: RevocationReason::LeftGuild,
+        },
+    );
+
+    // the pin stays with alice, but it no longer counts as a claim
+    assert_eq!(contract.pin_owner(0).await.unwrap(), Some(alice));
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    let pin_id = contract
+        .pin_id_by_address(alice, clp.guild_id, clp.action.clone())
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+    let pin_id = contract
+        .pin_id_by_user_id(clp.user_id, clp.guild_id, clp.action.clone())
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+
+    let metadata = contract.pin_metadata(0).await.unwrap();
+    let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(token_uri.attributes[6], Attribute::Status(Status::Revoked));
+    assert_eq!(
+        contract.metadata_status(0).await.unwrap().as_deref(),
+        Some("revoked")
+    );
+    let mint_date = token_uri
+        .attributes
+        .iter()
+        .find_map(|attr| attr.mint_date())
+        .unwrap();
+    let mut pin_data = PinData::from_claim(&clp, mint_date);
+    pin_data.revoked = true;
+    assert_eq!(pin_data.encode(0), metadata);
+
+    // the user may claim again after rejoining
+    let mut clp = clp;
+    clp.signed_at -= 1;
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+    let pin_id = contract
+        .pin_id_by_address(alice, clp.guild_id, clp.action.clone())
+        .await
+        .unwrap();
+    assert_eq!(pin_id, Some(1));
+
+    // revoked pins can still be burned by their owner
+    contract.burn(&parameters.alice, 0).await.unwrap();
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    let pin_id = contract
+        .pin_id_by_address(alice, clp.guild_id, clp.action)
+        .await
+        .unwrap();
+    assert_eq!(pin_id, Some(1));
+}
+
+#[tokio::test]
+async fn revoke_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let error = contract
+        .revoke(&parameters.owner, 0, RevocationReason::Other)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::PinIdDoesNotExist);
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let error = contract
+        .revoke(&parameters.alice, 0, RevocationReason::RoleRemoved)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    contract
+        .revoke(&parameters.owner, 0, RevocationReason::RoleRemoved)
+        .await
+        .unwrap();
+    let error = contract
+        .revoke(&parameters.owner, 0, RevocationReason::RoleRemoved)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AlreadyRevoked);
+
+    contract.burn(&parameters.alice, 0).await.unwrap();
+    let error = contract
+        .revoke(&parameters.owner, 0, RevocationReason::RoleRemoved)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AlreadyBurned);
+}
@@ -0,0 +1,122 @@
+use crate::check_error;
+use fuels::types::Address;
+use guild_pin_contract::contract::{GuildAction, GuildPinContract};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::metadata::{Action, Attribute, PinData, TokenUri};
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn claim_role_pins() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp_a = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Role(1))
+        .build();
+    let signature = parameters.sign_claim(&clp_a);
+    contract
+        .claim(&parameters.alice, clp_a.clone(), signature)
+        .await
+        .unwrap();
+
+    // another role in the same guild is a separate pin
+    let clp_b = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Role(2))
+        .build();
+    let signature = parameters.sign_claim(&clp_b);
+    contract
+        .claim(&parameters.alice, clp_b.clone(), signature)
+        .await
+        .unwrap();
+
+    // and so is joining the guild
+    let clp_joined =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp_joined);
+    contract
+        .claim(&parameters.alice, clp_joined, signature)
+        .await
+        .unwrap();
+
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 3);
+    assert_eq!(
+        contract
+            .total_minted_per_guild(clp_a.guild_id)
+            .await
+            .unwrap(),
+        3
+    );
+
+    let pin_id = contract
+        .pin_id_by_address(alice, clp_a.guild_id, GuildAction::Role(1))
+        .await
+        .unwrap();
+    assert_eq!(pin_id, Some(0));
+    let pin_id = contract
+        .pin_id_by_user_id(clp_b.user_id, clp_b.guild_id, GuildAction::Role(2))
+        .await
+        .unwrap();
+    assert_eq!(pin_id, Some(1));
+    let pin_id = contract
+        .pin_id_by_address(alice, clp_a.guild_id, GuildAction::Role(3))
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+
+    // the same role cannot be claimed twice
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Role(1))
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    let error = contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn role_pin_metadata() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp = ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider())
+        .action(GuildAction::Role(42))
+        .build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+
+    let metadata = contract.pin_metadata(0).await.unwrap();
+    let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(token_uri.name, Action::Role);
+    assert_eq!(token_uri.attributes[2], Attribute::RoleId(42));
+
+    let mint_date = token_uri
+        .attributes
+        .iter()
+        .find_map(|attr| attr.mint_date())
+        .unwrap();
+    assert_eq!(PinData::from_claim(&clp, mint_date).encode(0), metadata);
+
+    assert_eq!(contract.metadata_role_id(0).await.unwrap(), Some(42));
+    assert_eq!(
+        contract.metadata_type(0).await.unwrap(),
+        Some("Role in".to_string())
+    );
+
+    // other pins have no role id
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+    assert_eq!(contract.metadata_role_id(1).await.unwrap(), None);
+}
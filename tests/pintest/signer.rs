@@ -1,5 +1,6 @@
 use crate::{check_error, check_event};
 use guild_pin_contract::contract::{GuildPinContract, SignerChanged};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::parameters::ParametersBuilder;
 use signrs::Signer;
 
@@ -40,7 +41,7 @@ async fn set_signer_fails() {
         .set_signer(&parameters.bob, parameters.signer_alt_evm())
         .await
         .unwrap_err();
-    check_error(error, "NotOwner");
+    check_error(error, GuildPinError::NotOwner);
 
     let signer = contract.signer().await.unwrap();
     assert_eq!(signer, parameters.signer_evm());
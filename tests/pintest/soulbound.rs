@@ -0,0 +1,108 @@
+use crate::{check_error, check_event};
+use fuels::types::{Address, Identity};
+use guild_pin_contract::contract::{GuildPinContract, PinBurned};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::reconcile::reconcile;
+use guild_pin_contract::utils::ClaimBuilder;
+
+#[tokio::test]
+async fn soulbound_claim_keeps_custody() {
+    let parameters = ParametersBuilder::new().soulbound(true).test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+    assert!(contract.soulbound().await.unwrap());
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    // ownership is recorded, but the asset stays with the contract
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    assert_eq!(contract.pin_owner(0).await.unwrap(), Some(alice));
+    assert_eq!(contract.asset_supply(0).await.unwrap(), Some(1));
+    let held = parameters
+        .provider()
+        .get_asset_balance(parameters.alice.address(), contract.asset_id(0))
+        .await
+        .unwrap();
+    assert_eq!(held, 0);
+    let balances = contract.contract_balances().await.unwrap();
+    assert_eq!(balances.get(&contract.asset_id(0)), Some(&1));
+
+    // the contract holds every recorded pin, so there's no drift
+    let report = reconcile(&contract, parameters.provider(), &[])
+        .await
+        .unwrap();
+    assert_eq!(report.holders.len(), 1);
+    assert!(!report.has_drift());
+}
+
+#[tokio::test]
+async fn soulbound_burn_ok() {
+    let parameters = ParametersBuilder::new().soulbound(true).test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let error = contract.burn(&parameters.bob, 0).await.unwrap_err();
+    check_error(error, GuildPinError::NotPinOwner);
+
+    let response = contract.burn(&parameters.alice, 0).await.unwrap();
+    check_event(
+        response,
+        PinBurned {
+            pin_owner: alice,
+            pin_id: 0,
+        },
+    );
+
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 0);
+    assert_eq!(contract.asset_supply(0).await.unwrap(), Some(0));
+    let balances = contract.contract_balances().await.unwrap();
+    assert!(balances.is_empty());
+}
+
+#[tokio::test]
+async fn soulbound_withdraw_pin_fails() {
+    let parameters = ParametersBuilder::new().soulbound(true).test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let error = contract
+        .withdraw(
+            &parameters.owner,
+            contract.asset_id(0),
+            1,
+            Identity::Address(alice),
+        )
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::CannotWithdrawPin);
+}
+
+#[tokio::test]
+async fn default_mode_is_not_soulbound() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+    assert!(!contract.soulbound().await.unwrap());
+}
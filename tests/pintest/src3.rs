@@ -0,0 +1,151 @@
+use crate::{check_error, check_event};
+use fuels::prelude::CallParameters;
+use fuels::types::transaction_builders::VariableOutputPolicy;
+use fuels::types::{Address, Identity};
+use guild_pin_contract::contract::{GuildAction, GuildPinContract, PinBurned, PinMinted};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::{pin_sub_id, ClaimBuilder};
+
+#[tokio::test]
+async fn airdrop_mint_and_burn_ok() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+
+    let response = contract.mint(&parameters.owner, alice).await.unwrap();
+    check_event(
+        response,
+        PinMinted {
+            recipient: alice,
+            pin_id: 1,
+        },
+    );
+
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 2);
+    assert_eq!(contract.pin_owner(1).await.unwrap(), Some(alice));
+    assert_eq!(contract.asset_supply(1).await.unwrap(), Some(1));
+    let held = parameters
+        .provider()
+        .get_asset_balance(parameters.alice.address(), contract.asset_id(1))
+        .await
+        .unwrap();
+    assert_eq!(held, 1);
+
+    // airdrops are not bound to any guild, so the claimed pin is still the indexed one
+    let pin_id = contract
+        .pin_id_by_address(alice, clp.guild_id, clp.action.clone())
+        .await
+        .unwrap();
+    assert_eq!(pin_id, Some(0));
+    let pin_id = contract
+        .pin_id_by_address(alice, 0, GuildAction::Joined)
+        .await
+        .unwrap();
+    assert_eq!(pin_id, None);
+
+    // and their metadata holds placeholder guild data
+    assert_eq!(contract.metadata_guild_id(1).await.unwrap(), Some(0));
+    assert_eq!(
+        contract.metadata_type(1).await.unwrap(),
+        Some("Joined".to_string())
+    );
+
+    let response = contract.burn(&parameters.alice, 1).await.unwrap();
+    check_event(
+        response,
+        PinBurned {
+            pin_owner: alice,
+            pin_id: 1,
+        },
+    );
+    assert_eq!(contract.balance_of(alice).await.unwrap(), 1);
+    assert_eq!(
+        contract.token_of_owner_by_index(alice, 0).await.unwrap(),
+        Some(0)
+    );
+    assert_eq!(contract.total_supply().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn airdrop_mint_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let error = contract.mint(&parameters.alice, alice).await.unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    let methods = contract.inner().methods();
+    // the sub id has to belong to the next pin
+    let error: GuildPinError = methods
+        .mint(Identity::Address(alice), pin_sub_id(1), 1)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap_err()
+        .into();
+    check_error(error, GuildPinError::InvalidSubId);
+
+    // every pin is a distinct asset with a supply of 1
+    let error: GuildPinError = methods
+        .mint(Identity::Address(alice), pin_sub_id(0), 2)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap_err()
+        .into();
+    check_error(error, GuildPinError::InvalidAmount);
+
+    // pins are owned by addresses only
+    let error: GuildPinError = methods
+        .mint(
+            Identity::ContractId(contract.contract_id()),
+            pin_sub_id(0),
+            1,
+        )
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap_err()
+        .into();
+    check_error(error, GuildPinError::InvalidRecipient);
+
+    assert_eq!(contract.total_minted().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn burn_invalid_amount_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    contract.mint(&parameters.owner, alice).await.unwrap();
+
+    let error: GuildPinError = contract
+        .inner()
+        .clone()
+        .with_account(parameters.alice.clone())
+        .methods()
+        .burn(pin_sub_id(0), 2)
+        .call_params(
+            CallParameters::default()
+                .with_asset_id(contract.asset_id(0))
+                .with_amount(1),
+        )
+        .unwrap()
+        .call()
+        .await
+        .unwrap_err()
+        .into();
+    check_error(error, GuildPinError::InvalidAmount);
+    assert_eq!(contract.pin_owner(0).await.unwrap(), Some(alice));
+}
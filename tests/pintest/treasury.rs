@@ -1,5 +1,6 @@
 use crate::{check_error, check_event};
 use guild_pin_contract::contract::{GuildPinContract, TreasuryChanged};
+use guild_pin_contract::error::GuildPinError;
 use guild_pin_contract::parameters::ParametersBuilder;
 
 #[tokio::test]
@@ -35,7 +36,7 @@ async fn set_treasury_fails() {
         .set_treasury(&parameters.bob, parameters.bob_id())
         .await
         .unwrap_err();
-    check_error(error, "NotOwner");
+    check_error(error, GuildPinError::NotOwner);
 
     let treasury = contract.treasury().await.unwrap();
     assert_eq!(treasury, parameters.treasury_id());
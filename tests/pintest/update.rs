@@ -0,0 +1,189 @@
+use crate::{check_error, check_event};
+use fuels::types::{Address, SizedAsciiString};
+use guild_pin_contract::contract::{GuildPinContract, PinMetadataUpdated};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::metadata::{PinData, TokenUri};
+use guild_pin_contract::parameters::ParametersBuilder;
+use guild_pin_contract::utils::{ClaimBuilder, UpdateBuilder};
+
+const NEW_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+#[tokio::test]
+async fn update_pin_metadata_ok() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp.clone(), signature)
+        .await
+        .unwrap();
+
+    let update = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .guild_name("MyRenamedGuild")
+        .cid(NEW_CID)
+        .build();
+    let signature = parameters.sign_update(&update);
+    // anyone may submit the signed update
+    let response = contract
+        .update_pin_metadata(&parameters.bob, update.clone(), signature)
+        .await
+        .unwrap();
+    check_event(
+        response,
+        PinMetadataUpdated {
+            pin_id: 0,
+            guild_name: update.guild_name.clone(),
+            cid: update.cid.clone(),
+        },
+    );
+
+    let metadata = contract.pin_metadata(0).await.unwrap();
+    let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(
+        token_uri.description,
+        "This is an onchain proof that you joined MyRenamedGuild on Guild.xyz"
+    );
+    assert_eq!(token_uri.image, format!("ipfs://{}", NEW_CID));
+
+    // everything else is left untouched
+    let mint_date = token_uri
+        .attributes
+        .iter()
+        .find_map(|attr| attr.mint_date())
+        .unwrap();
+    let mut pin_data = PinData::from_claim(&clp, mint_date);
+    pin_data.guild_name =
+        SizedAsciiString::new_with_right_whitespace_padding("MyRenamedGuild".to_string()).unwrap();
+    pin_data.cid =
+        SizedAsciiString::new_with_right_whitespace_padding(NEW_CID.to_string()).unwrap();
+    assert_eq!(pin_data.encode(0), metadata);
+
+    // the same update cannot be replayed
+    let error = contract
+        .update_pin_metadata(&parameters.bob, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::MessageAlreadyConsumed);
+}
+
+#[tokio::test]
+async fn update_pin_metadata_fails() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    // nonexistent pin
+    let update =
+        UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_update(&update);
+    let error = contract
+        .update_pin_metadata(&parameters.alice, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::PinIdDoesNotExist);
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    // invalid signer
+    let update = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .guild_name("Hijacked")
+        .build();
+    let signature = parameters.sign_alt_update(&update);
+    let error = contract
+        .update_pin_metadata(&parameters.alice, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::InvalidSignature);
+
+    // expired signature
+    let update = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .signed_at(parameters.timestamp().await - 4000)
+        .build();
+    let signature = parameters.sign_update(&update);
+    let error = contract
+        .update_pin_metadata(&parameters.alice, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::ExpiredSignature);
+
+    // wrong chain
+    let update = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .chain_id(u64::MAX)
+        .build();
+    let signature = parameters.sign_update(&update);
+    let error = contract
+        .update_pin_metadata(&parameters.alice, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::InvalidChainId);
+
+    // burned pin
+    contract.burn(&parameters.alice, 0).await.unwrap();
+    let update =
+        UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_update(&update);
+    let error = contract
+        .update_pin_metadata(&parameters.alice, update, signature)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::PinIdDoesNotExist);
+}
+
+#[tokio::test]
+async fn update_pin_metadata_in_signing_order() {
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let alice: Address = parameters.alice.address().into();
+    let clp =
+        ClaimBuilder::for_provider(alice, contract.contract_id(), parameters.provider()).build();
+    let signature = parameters.sign_claim(&clp);
+    contract
+        .claim(&parameters.alice, clp, signature)
+        .await
+        .unwrap();
+
+    let timestamp = parameters.timestamp().await;
+    let update_a = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .guild_name("OlderName")
+        .signed_at(timestamp - 10)
+        .build();
+    let signature_a = parameters.sign_update(&update_a);
+    let update_b = UpdateBuilder::for_provider(0, contract.contract_id(), parameters.provider())
+        .guild_name("NewerName")
+        .cid(NEW_CID)
+        .signed_at(timestamp - 5)
+        .build();
+    let signature_b = parameters.sign_update(&update_b);
+
+    // the newer update lands first
+    contract
+        .update_pin_metadata(&parameters.bob, update_b, signature_b)
+        .await
+        .unwrap();
+
+    // so the older one can't overwrite it
+    let error = contract
+        .update_pin_metadata(&parameters.bob, update_a, signature_a)
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::StaleUpdate);
+
+    let metadata = contract.pin_metadata(0).await.unwrap();
+    let token_uri: TokenUri = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(
+        token_uri.description,
+        "This is an onchain proof that you joined NewerName on Guild.xyz"
+    );
+    assert_eq!(token_uri.image, format!("ipfs://{}", NEW_CID));
+}
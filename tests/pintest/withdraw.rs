@@ -0,0 +1,118 @@
+use crate::{check_error, check_event};
+use fuels::prelude::{Account, TxPolicies};
+use fuels::types::{Identity, Salt};
+use guild_pin_contract::contract::{GuildPinContract, Withdrawn};
+use guild_pin_contract::error::GuildPinError;
+use guild_pin_contract::parameters::{ParametersBuilder, TEST_ASSET_ID};
+
+#[tokio::test]
+async fn withdraw_success() {
+    let genesis_balance = 100;
+    let stray_amount = 40;
+    let parameters = ParametersBuilder::new()
+        .genesis_balance(genesis_balance)
+        .test()
+        .await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    let balances = contract.contract_balances().await.unwrap();
+    assert!(balances.is_empty());
+
+    // assets sent to the contract by mistake
+    parameters
+        .alice
+        .force_transfer_to_contract(
+            contract.bech_contract_id(),
+            stray_amount,
+            TEST_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let balances = contract.contract_balances().await.unwrap();
+    assert_eq!(balances.get(&TEST_ASSET_ID), Some(&stray_amount));
+
+    let response = contract
+        .withdraw(
+            &parameters.owner,
+            TEST_ASSET_ID,
+            stray_amount / 2,
+            parameters.alice_id(),
+        )
+        .await
+        .unwrap();
+    check_event(
+        response,
+        Withdrawn {
+            asset: TEST_ASSET_ID,
+            amount: stray_amount / 2,
+            to: parameters.alice_id(),
+        },
+    );
+
+    let alice_balance = parameters
+        .provider()
+        .get_asset_balance(parameters.alice.address(), TEST_ASSET_ID)
+        .await
+        .unwrap();
+    assert_eq!(alice_balance, genesis_balance - stray_amount / 2);
+
+    // sweep the rest to another contract
+    let mut parameters = parameters;
+    parameters.salt = Salt::from([1u8; 32]);
+    let other_contract = GuildPinContract::deploy(&parameters).await;
+    let to = Identity::ContractId(other_contract.contract_id());
+    contract
+        .withdraw(&parameters.owner, TEST_ASSET_ID, stray_amount / 2, to)
+        .await
+        .unwrap();
+
+    let balances = contract.contract_balances().await.unwrap();
+    assert!(balances.is_empty());
+    let balances = other_contract.contract_balances().await.unwrap();
+    assert_eq!(balances.get(&TEST_ASSET_ID), Some(&(stray_amount / 2)));
+}
+
+#[tokio::test]
+async fn withdraw_fails() {
+    let stray_amount = 40;
+    let parameters = ParametersBuilder::new().test().await;
+    let contract = GuildPinContract::init(&parameters).await;
+
+    parameters
+        .alice
+        .force_transfer_to_contract(
+            contract.bech_contract_id(),
+            stray_amount,
+            TEST_ASSET_ID,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let error = contract
+        .withdraw(
+            &parameters.bob,
+            TEST_ASSET_ID,
+            stray_amount,
+            parameters.bob_id(),
+        )
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::NotOwner);
+
+    let error = contract
+        .withdraw(
+            &parameters.owner,
+            TEST_ASSET_ID,
+            stray_amount + 1,
+            parameters.owner_id(),
+        )
+        .await
+        .unwrap_err();
+    check_error(error, GuildPinError::InsufficientBalance);
+
+    let balances = contract.contract_balances().await.unwrap();
+    assert_eq!(balances.get(&TEST_ASSET_ID), Some(&stray_amount));
+}